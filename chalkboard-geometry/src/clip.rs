@@ -3,11 +3,48 @@
 //       (See accompanying file LICENSE or copy at
 //         https://www.boost.org/LICENSE_1_0.txt)
 
-use super::{PathBuffer, Region};
+use super::{translate_paths, Box2D, PathBuffer, Region, Vector2D};
 
 /// The clipping region for a given operation.
+#[derive(Clone)]
 pub struct Clip {
     // the clip is a combination of the path (closed) and the region
     path: PathBuffer,
     region: Region<f32>,
 }
+
+impl Clip {
+    /// Create a new `Clip` from a closed path and a region.
+    ///
+    /// An empty path does not restrict the clip any further than the
+    /// region does.
+    pub fn new(path: PathBuffer, region: Region<f32>) -> Self {
+        Self { path, region }
+    }
+
+    /// Get the closed path that this clip is bounded by.
+    pub fn path(&self) -> &PathBuffer {
+        &self.path
+    }
+
+    /// Get the region that this clip is bounded by.
+    pub fn region(&self) -> &Region<f32> {
+        &self.region
+    }
+
+    /// Get the part of this clip that lies within a box.
+    pub fn intersection(&self, other: &Box2D<f32>) -> Self {
+        Self {
+            path: self.path.clone(),
+            region: self.region.intersection(other),
+        }
+    }
+
+    /// Get this clip, moved by the given vector.
+    pub fn translate(&self, by: Vector2D<f32>) -> Self {
+        Self {
+            path: translate_paths(&self.path, by),
+            region: self.region.translate(by),
+        }
+    }
+}
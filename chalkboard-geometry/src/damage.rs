@@ -0,0 +1,141 @@
+//               Copyright John Nunley, 2022.
+// Distributed under the Boost Software License, Version 1.0.
+//       (See accompanying file LICENSE or copy at
+//         https://www.boost.org/LICENSE_1_0.txt)
+
+use super::{Box2D, Region};
+use alloc::vec::Vec;
+use num_traits::Bounded;
+
+const DEFAULT_MAX_BOXES: usize = 32;
+
+/// Collects the areas invalidated over the course of a frame.
+///
+/// Overlapping boxes are merged as they are added. If the number of
+/// boxes grows beyond the limit, they are all collapsed into their
+/// bounding box, since at that point drawing a little extra is cheaper
+/// than tracking every box.
+#[derive(Debug, Clone)]
+pub struct DamageAccumulator<T> {
+    /// The disjoint boxes damaged so far.
+    boxes: Vec<Box2D<T>>,
+    /// The maximum number of boxes to track.
+    max_boxes: usize,
+}
+
+impl<T> Default for DamageAccumulator<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_BOXES)
+    }
+}
+
+impl<T> DamageAccumulator<T> {
+    /// Create a new `DamageAccumulator` that tracks, at most, `max_boxes`
+    /// boxes.
+    pub fn new(max_boxes: usize) -> Self {
+        Self {
+            boxes: Vec::new(),
+            max_boxes: max_boxes.max(1),
+        }
+    }
+
+    /// Tell whether or not anything has been damaged.
+    pub fn is_empty(&self) -> bool {
+        self.boxes.is_empty()
+    }
+
+    /// Forget everything that has been damaged.
+    pub fn clear(&mut self) {
+        self.boxes.clear();
+    }
+
+    /// Get the boxes damaged so far.
+    pub fn boxes(&self) -> &[Box2D<T>] {
+        &self.boxes
+    }
+}
+
+impl<T: Copy + PartialOrd> DamageAccumulator<T> {
+    /// Mark a box as damaged.
+    pub fn add(&mut self, mut box_: Box2D<T>) {
+        if box_.is_empty() {
+            return;
+        }
+
+        // absorb every box that overlaps with this one; absorbing a box
+        // may cause the union to overlap boxes that were checked earlier,
+        // so repeat until nothing overlaps
+        while let Some(i) = self.boxes.iter().position(|other| other.intersects(&box_)) {
+            box_ = box_.union(&self.boxes.swap_remove(i));
+        }
+
+        self.boxes.push(box_);
+
+        if self.boxes.len() > self.max_boxes {
+            let bounds = self
+                .boxes
+                .iter()
+                .skip(1)
+                .fold(self.boxes[0], |bounds, box_| bounds.union(box_));
+            self.boxes.clear();
+            self.boxes.push(bounds);
+        }
+    }
+}
+
+impl<T: Bounded + Copy + PartialOrd> DamageAccumulator<T> {
+    /// Get the damaged area as a `Region`.
+    pub fn region(&self) -> Region<T> {
+        self.boxes.iter().copied().collect()
+    }
+
+    /// Take the damaged area as a `Region`, leaving this accumulator
+    /// empty for the next frame.
+    pub fn take_region(&mut self) -> Region<T> {
+        let region = self.region();
+        self.clear();
+        region
+    }
+}
+
+impl<T: Copy + PartialOrd> Extend<Box2D<T>> for DamageAccumulator<T> {
+    fn extend<I: IntoIterator<Item = Box2D<T>>>(&mut self, iter: I) {
+        for box_ in iter {
+            self.add(box_);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Point2D;
+
+    fn boxed(x1: i32, y1: i32, x2: i32, y2: i32) -> Box2D<i32> {
+        Box2D::new(Point2D::new(x1, y1), Point2D::new(x2, y2))
+    }
+
+    #[test]
+    fn test_damage_merging() {
+        let mut damage = DamageAccumulator::new(2);
+
+        // disjoint boxes stay separate, empty boxes are ignored
+        damage.add(boxed(0, 0, 10, 10));
+        damage.add(boxed(20, 0, 30, 10));
+        damage.add(boxed(5, 5, 5, 5));
+        assert_eq!(damage.boxes().len(), 2);
+
+        // a box bridging both merges them together
+        damage.add(boxed(5, 0, 25, 5));
+        assert_eq!(damage.boxes(), &[boxed(0, 0, 30, 10)]);
+
+        // going over the limit collapses to the bounding box
+        damage.add(boxed(0, 20, 5, 25));
+        damage.add(boxed(40, 40, 50, 50));
+        assert_eq!(damage.boxes(), &[boxed(0, 0, 50, 50)]);
+
+        let region = damage.take_region();
+        assert_eq!(region.bounds(), boxed(0, 0, 50, 50));
+        assert!(damage.is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+//               Copyright John Nunley, 2022.
+// Distributed under the Boost Software License, Version 1.0.
+//       (See accompanying file LICENSE or copy at
+//         https://www.boost.org/LICENSE_1_0.txt)
+
+//! Conversions to and from 16.16 fixed-point numbers.
+//!
+//! This is the format used by XRender and several other backends for
+//! sub-pixel coordinates. Conversions into fixed-point are checked, since
+//! a plain cast or shift silently wraps values that are out of range.
+
+use num_traits::Float;
+
+/// A 16.16 fixed-point number.
+pub type Fixed = i32;
+
+/// The fixed-point representation of `1.0`.
+pub const FIXED_ONE: Fixed = 1 << 16;
+
+/// Convert a floating-point number to fixed-point, rounding to the
+/// nearest representable value.
+///
+/// Returns `None` if the value is not finite or is outside of the range
+/// that can be represented, which is roughly `-32768.0..32768.0`.
+pub fn double_to_fixed(value: f64) -> Option<Fixed> {
+    let scaled = Float::round(value * f64::from(FIXED_ONE));
+
+    // also rejects NaN, for which both comparisons are false
+    if scaled >= f64::from(Fixed::MIN) && scaled <= f64::from(Fixed::MAX) {
+        Some(scaled as Fixed)
+    } else {
+        None
+    }
+}
+
+/// Convert a floating-point number to fixed-point, clamping values that
+/// are out of range to its limits.
+///
+/// NaN is converted to zero.
+pub fn double_to_fixed_saturating(value: f64) -> Fixed {
+    // float to integer casts saturate, and turn NaN into zero
+    Float::round(value * f64::from(FIXED_ONE)) as Fixed
+}
+
+/// Convert a fixed-point number to floating-point.
+///
+/// This is always exact.
+pub fn fixed_to_double(value: Fixed) -> f64 {
+    f64::from(value) / f64::from(FIXED_ONE)
+}
+
+/// Convert an integer to fixed-point.
+///
+/// Returns `None` if the integer is outside of the range that can be
+/// represented.
+pub fn int_to_fixed(value: i32) -> Option<Fixed> {
+    value.checked_mul(FIXED_ONE)
+}
+
+/// Get the integer part of a fixed-point number, rounding towards negative
+/// infinity.
+pub fn fixed_to_int(value: Fixed) -> i32 {
+    value >> 16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_roundtrip() {
+        for &value in &[0.0, 1.0, -1.0, 0.5, -0.5, 1234.25, -32768.0, 32767.5] {
+            let fixed = double_to_fixed(value).unwrap();
+            assert_eq!(fixed_to_double(fixed), value);
+        }
+
+        assert_eq!(double_to_fixed(1.0), Some(FIXED_ONE));
+        assert_eq!(double_to_fixed(-1.0), Some(-FIXED_ONE));
+
+        // rounds to the nearest representable value
+        let step = fixed_to_double(1);
+        assert_eq!(double_to_fixed(step * 0.6), Some(1));
+        assert_eq!(double_to_fixed(-step * 0.6), Some(-1));
+    }
+
+    #[test]
+    fn test_fixed_out_of_range() {
+        assert_eq!(double_to_fixed(32768.0), None);
+        assert_eq!(double_to_fixed(-32768.1), None);
+        assert_eq!(double_to_fixed(f64::NAN), None);
+        assert_eq!(double_to_fixed(f64::INFINITY), None);
+
+        assert_eq!(double_to_fixed_saturating(1.0e9), Fixed::MAX);
+        assert_eq!(double_to_fixed_saturating(-1.0e9), Fixed::MIN);
+        assert_eq!(double_to_fixed_saturating(f64::NAN), 0);
+
+        assert_eq!(int_to_fixed(-3), Some(-3 * FIXED_ONE));
+        assert_eq!(int_to_fixed(32767), Some(32767 * FIXED_ONE));
+        assert_eq!(int_to_fixed(32768), None);
+        assert_eq!(int_to_fixed(-32769), None);
+
+        // the integer part rounds down, including for negative values
+        assert_eq!(fixed_to_int(double_to_fixed(2.5).unwrap()), 2);
+        assert_eq!(fixed_to_int(double_to_fixed(-2.5).unwrap()), -3);
+    }
+}
@@ -8,8 +8,17 @@
 //! These are mostly re-exports from [`euclid`] and [`lyon`], but there are certain new
 //! primitives that are essential for rendering.
 //!
+//! Enabling the `mint` feature adds conversions between the [`euclid`] types
+//! re-exported here and their [`mint`] equivalents, so that points, vectors and
+//! transforms from other math libraries can be passed in directly.
+//!
+//! Non-finite edges are dropped when building a [`Polygon`]. Enabling the
+//! `assert-finite` feature turns these into debug assertions, which helps
+//! track down where the bad coordinates come from.
+//!
 //! [`euclid`]: https://docs.rs/euclid/
 //! [`lyon`]: https://docs.rs/lyon/
+//! [`mint`]: https://docs.rs/mint/
 
 #![no_std]
 #![forbid(unsafe_code, rust_2018_idioms)]
@@ -22,18 +31,35 @@ pub use clip::Clip;
 mod composite;
 pub use composite::CompositeOperation;
 
+mod damage;
+pub use damage::DamageAccumulator;
+
+pub mod fixed;
+
+mod offset;
+pub use offset::{offset_path, LineJoin};
+
+mod paths;
+pub use paths::{map_paths, translate_paths};
+
 mod region;
 pub use region::Region;
 
 mod slope;
 pub use slope::Slope;
 
+mod shapes;
+pub use shapes::ring_segment;
+
 mod thrice;
 pub(crate) use thrice::Thrice;
 
 mod trap;
 pub use trap::Trapezoid;
 
+mod plot;
+pub use plot::{polyline_from_samples, LineMode};
+
 mod polygon;
 pub use polygon::*;
 
@@ -0,0 +1,313 @@
+//               Copyright John Nunley, 2022.
+// Distributed under the Boost Software License, Version 1.0.
+//       (See accompanying file LICENSE or copy at
+//         https://www.boost.org/LICENSE_1_0.txt)
+
+use super::{Arc, Path, PathEvent, PathSlice, Point2D, Vector2D};
+use crate::util::approx_eq;
+use alloc::vec::Vec;
+use lyon_path::iterator::PathIterator;
+use num_traits::Float;
+
+/// The tolerance used to flatten curves, both in the input path and in
+/// round joins.
+const TOLERANCE: f32 = 0.1;
+
+/// The shape used to join two offset edges at an outer corner.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum LineJoin {
+    /// Extend the edges until they meet, unless that would exceed the
+    /// miter limit, in which case a bevel is used instead.
+    #[default]
+    Miter,
+    /// Connect the edges with a straight line.
+    Bevel,
+    /// Connect the edges with an arc.
+    Round,
+}
+
+/// Offset the area covered by a path by the given distance.
+///
+/// Positive distances grow the area outwards and negative distances
+/// shrink it inwards. A subpath that lies inside of an odd number of
+/// other subpaths is a hole, so it shrinks as the area grows and the
+/// other way around. This is decided by nesting alone, so the direction
+/// each subpath is wound in does not matter.
+///
+/// Every subpath is treated as closed, and curves are flattened before
+/// offsetting, so the result only contains straight lines.
+///
+/// `miter_limit` is the maximum ratio between the length of a miter and
+/// the distance. It is only used for `LineJoin::Miter`.
+///
+/// This does not remove the self-intersections that may appear when
+/// insetting by more than the width of a feature.
+pub fn offset_path(path: PathSlice<'_>, distance: f32, join: LineJoin, miter_limit: f32) -> Path {
+    let mut polygons: Vec<Vec<Point2D<f32>>> = Vec::new();
+
+    for event in path.iter().flattened(TOLERANCE) {
+        match event {
+            PathEvent::Begin { at } => polygons.push(alloc::vec![at]),
+            PathEvent::Line { to, .. } => {
+                let points = polygons.last_mut().unwrap();
+
+                // skip zero-length edges
+                if !approx_eq_pt(points.last().unwrap(), &to) {
+                    points.push(to);
+                }
+            }
+            PathEvent::End { .. } => {
+                let points = polygons.last_mut().unwrap();
+
+                // the closing edge is implied
+                while points.len() > 1 && approx_eq_pt(&points[0], points.last().unwrap()) {
+                    points.pop();
+                }
+            }
+            ev => unreachable!("Flattened iterator should never yield {:?}", ev),
+        }
+    }
+
+    let mut builder = Path::builder();
+
+    for (i, points) in polygons.iter().enumerate() {
+        // holes move the other way to the shapes around them
+        let depth = polygons
+            .iter()
+            .enumerate()
+            .filter(|&(j, other)| j != i && contains(other, points[0]))
+            .count();
+        let distance = if depth % 2 == 0 { distance } else { -distance };
+
+        let offset = offset_polygon(points, distance, join, miter_limit);
+        if let Some((first, rest)) = offset.split_first() {
+            builder.begin(*first);
+            for pt in rest {
+                builder.line_to(*pt);
+            }
+            builder.close();
+        }
+    }
+
+    builder.build()
+}
+
+/// Tell whether a point lies inside of a closed polygon, using the
+/// even-odd rule.
+fn contains(polygon: &[Point2D<f32>], pt: Point2D<f32>) -> bool {
+    let mut inside = false;
+
+    for (a, b) in polygon.iter().zip(polygon.iter().cycle().skip(1)) {
+        // count the edges crossed by a ray going right from the point
+        if (a.y > pt.y) != (b.y > pt.y) {
+            let x = a.x + (pt.y - a.y) / (b.y - a.y) * (b.x - a.x);
+            if pt.x < x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Offset a single closed polygon.
+fn offset_polygon(
+    points: &[Point2D<f32>],
+    distance: f32,
+    join: LineJoin,
+    miter_limit: f32,
+) -> Vec<Point2D<f32>> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+
+    // use the winding of the polygon to find which side is "outside"
+    let area = points
+        .iter()
+        .zip(points.iter().cycle().skip(1))
+        .map(|(a, b)| a.to_vector().cross(b.to_vector()))
+        .sum::<f32>();
+    if approx_eq(area, 0.0) {
+        return Vec::new();
+    }
+    let side = area.signum() * distance;
+
+    let normal = |a: Point2D<f32>, b: Point2D<f32>| {
+        let dir = (b - a).normalize();
+        Vector2D::new(dir.y, -dir.x) * area.signum()
+    };
+
+    let len = points.len();
+    let mut result = Vec::with_capacity(len);
+
+    for i in 0..len {
+        let prev = points[(i + len - 1) % len];
+        let pt = points[i];
+        let next = points[(i + 1) % len];
+
+        let n0 = normal(prev, pt);
+        let n1 = normal(pt, next);
+        let a = pt + n0 * distance;
+        let b = pt + n1 * distance;
+
+        // the corner is on the outside of the offset if the edges turn
+        // away from it
+        let outer = (pt - prev).cross(next - pt) * side > 0.0;
+        let cos = n0.dot(n1);
+
+        // the point where the two offset edges meet
+        let miter = if cos > -1.0 + f32::EPSILON {
+            Some(pt + (n0 + n1) * (distance / (1.0 + cos)))
+        } else {
+            None
+        };
+
+        match (outer, join, miter) {
+            // edges that don't turn or meet on the inside of the offset
+            (false, _, Some(miter)) => result.push(miter),
+            (true, LineJoin::Miter, Some(miter))
+                if Float::sqrt(2.0 / (1.0 + cos)) <= miter_limit =>
+            {
+                result.push(miter)
+            }
+            (true, LineJoin::Round, _) => {
+                let arc = Arc {
+                    center: pt,
+                    radii: Vector2D::new(distance.abs(), distance.abs()),
+                    start_angle: (a - pt).angle_from_x_axis(),
+                    sweep_angle: (a - pt).angle_to(b - pt),
+                    x_rotation: Default::default(),
+                };
+
+                result.push(a);
+                arc.for_each_flattened(TOLERANCE, &mut |seg| result.push(seg.to));
+            }
+            _ => {
+                result.push(a);
+                result.push(b);
+            }
+        }
+    }
+
+    result
+}
+
+fn approx_eq_pt(a: &Point2D<f32>, b: &Point2D<f32>) -> bool {
+    approx_eq(a.x, b.x) && approx_eq(a.y, b.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Box2D;
+
+    fn square_points(min: f32, max: f32, clockwise: bool) -> [Point2D<f32>; 4] {
+        let mut pts = [
+            Point2D::new(min, min),
+            Point2D::new(max, min),
+            Point2D::new(max, max),
+            Point2D::new(min, max),
+        ];
+        if clockwise {
+            pts.reverse();
+        }
+        pts
+    }
+
+    fn polygons(subpaths: &[[Point2D<f32>; 4]]) -> Path {
+        let mut builder = Path::builder();
+        for pts in subpaths {
+            builder.begin(pts[0]);
+            for pt in &pts[1..] {
+                builder.line_to(*pt);
+            }
+            builder.close();
+        }
+        builder.build()
+    }
+
+    fn square(clockwise: bool) -> Path {
+        polygons(&[square_points(0.0, 10.0, clockwise)])
+    }
+
+    fn endpoints(path: &Path) -> Vec<Point2D<f32>> {
+        path.iter()
+            .filter_map(|event| match event {
+                PathEvent::Begin { at } => Some(at),
+                PathEvent::Line { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn bounds(path: &Path) -> Box2D<f32> {
+        Box2D::from_points(endpoints(path))
+    }
+
+    #[test]
+    fn test_offset_miter() {
+        for &clockwise in &[false, true] {
+            let path = square(clockwise);
+
+            let outset = offset_path(path.as_slice(), 1.0, LineJoin::Miter, 4.0);
+            assert_eq!(endpoints(&outset).len(), 4);
+            assert_eq!(
+                bounds(&outset),
+                Box2D::new(Point2D::new(-1.0, -1.0), Point2D::new(11.0, 11.0))
+            );
+
+            let inset = offset_path(path.as_slice(), -1.0, LineJoin::Miter, 4.0);
+            assert_eq!(endpoints(&inset).len(), 4);
+            assert_eq!(
+                bounds(&inset),
+                Box2D::new(Point2D::new(1.0, 1.0), Point2D::new(9.0, 9.0))
+            );
+        }
+    }
+
+    #[test]
+    fn test_offset_joins() {
+        let path = square(false);
+
+        // a bevel cuts every corner, as does a miter past its limit
+        let bevel = offset_path(path.as_slice(), 1.0, LineJoin::Bevel, 4.0);
+        assert_eq!(endpoints(&bevel).len(), 8);
+        let limited = offset_path(path.as_slice(), 1.0, LineJoin::Miter, 1.0);
+        assert_eq!(endpoints(&limited).len(), 8);
+
+        // round corners stay the distance away from the corner
+        let round = offset_path(path.as_slice(), 1.0, LineJoin::Round, 4.0);
+        assert!(endpoints(&round).len() > 8);
+        let b = bounds(&round);
+        assert!(approx_eq(b.min.x, -1.0) && approx_eq(b.max.y, 11.0));
+    }
+
+    #[test]
+    fn test_offset_holes() {
+        // holes wound either way are moved against the outer square
+        for &clockwise in &[false, true] {
+            let path = polygons(&[
+                square_points(0.0, 10.0, false),
+                square_points(3.0, 7.0, clockwise),
+            ]);
+
+            let outset = offset_path(path.as_slice(), 1.0, LineJoin::Miter, 4.0);
+            let subpaths = endpoints(&outset);
+            assert_eq!(
+                Box2D::from_points(&subpaths[..4]),
+                Box2D::new(Point2D::new(-1.0, -1.0), Point2D::new(11.0, 11.0))
+            );
+            assert_eq!(
+                Box2D::from_points(&subpaths[4..]),
+                Box2D::new(Point2D::new(4.0, 4.0), Point2D::new(6.0, 6.0))
+            );
+
+            let inset = offset_path(path.as_slice(), -1.0, LineJoin::Miter, 4.0);
+            let subpaths = endpoints(&inset);
+            assert_eq!(
+                Box2D::from_points(&subpaths[4..]),
+                Box2D::new(Point2D::new(2.0, 2.0), Point2D::new(8.0, 8.0))
+            );
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//               Copyright John Nunley, 2022.
+// Distributed under the Boost Software License, Version 1.0.
+//       (See accompanying file LICENSE or copy at
+//         https://www.boost.org/LICENSE_1_0.txt)
+
+use super::{PathBuffer, PathBuilder, PathEvent, Point2D, Vector2D};
+use lyon_path::NO_ATTRIBUTES;
+
+/// Get a copy of every path in the buffer, with every point passed
+/// through `f`.
+///
+/// Points shared between two events, such as the end of one line and
+/// the start of the next, are passed to `f` once for each event.
+pub fn map_paths(
+    paths: &PathBuffer,
+    mut f: impl FnMut(Point2D<f32>) -> Point2D<f32>,
+) -> PathBuffer {
+    let mut mapped = PathBuffer::with_capacity(0, 0, paths.len());
+
+    for path in paths.iter() {
+        let mut builder = mapped.builder();
+        for event in path.iter() {
+            let event = match event {
+                PathEvent::Begin { at } => PathEvent::Begin { at: f(at) },
+                PathEvent::Line { from, to } => PathEvent::Line {
+                    from: f(from),
+                    to: f(to),
+                },
+                PathEvent::Quadratic { from, ctrl, to } => PathEvent::Quadratic {
+                    from: f(from),
+                    ctrl: f(ctrl),
+                    to: f(to),
+                },
+                PathEvent::Cubic {
+                    from,
+                    ctrl1,
+                    ctrl2,
+                    to,
+                } => PathEvent::Cubic {
+                    from: f(from),
+                    ctrl1: f(ctrl1),
+                    ctrl2: f(ctrl2),
+                    to: f(to),
+                },
+                PathEvent::End { last, first, close } => PathEvent::End {
+                    last: f(last),
+                    first: f(first),
+                    close,
+                },
+            };
+            builder.path_event(event, NO_ATTRIBUTES);
+        }
+        builder.build();
+    }
+
+    mapped
+}
+
+/// Get a copy of every path in the buffer, moved by the given vector.
+pub fn translate_paths(paths: &PathBuffer, by: Vector2D<f32>) -> PathBuffer {
+    map_paths(paths, |pt| pt + by)
+}
@@ -0,0 +1,234 @@
+//               Copyright John Nunley, 2022.
+// Distributed under the Boost Software License, Version 1.0.
+//       (See accompanying file LICENSE or copy at
+//         https://www.boost.org/LICENSE_1_0.txt)
+
+use super::{LineSegment, Path, Point2D};
+use alloc::{vec, vec::Vec};
+
+/// The way that the samples of a plot are connected.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum LineMode {
+    /// Connect the samples with straight lines.
+    #[default]
+    Linear,
+    /// Hold each sample until the next one, then jump to it.
+    Step,
+    /// Connect the samples with a smooth curve.
+    ///
+    /// The curve passes through every sample that is kept after
+    /// decimation, and never overshoots them: it only rises or falls
+    /// where the samples do, and never runs backwards along the x axis.
+    Smooth,
+}
+
+/// Build a path plotting the given samples.
+///
+/// The sample at index `i` is placed at `(i, samples[i])`; transform the
+/// resulting path to fit it into the chart's axes.
+///
+/// Points that are within `decimate_tolerance` of the line through their
+/// neighbors are removed before the path is built, which greatly reduces
+/// the size of the path for dense or flat data. A tolerance of zero only
+/// removes points that are exactly collinear.
+pub fn polyline_from_samples(samples: &[f32], mode: LineMode, decimate_tolerance: f32) -> Path {
+    let points = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &y)| Point2D::new(i as f32, y));
+
+    let points = match mode {
+        LineMode::Linear | LineMode::Smooth => points.collect::<Vec<_>>(),
+        LineMode::Step => {
+            let mut steps: Vec<Point2D<f32>> = Vec::with_capacity(samples.len() * 2);
+            for pt in points {
+                if let Some(last) = steps.last().copied() {
+                    steps.push(Point2D::new(pt.x, last.y));
+                }
+                steps.push(pt);
+            }
+            steps
+        }
+    };
+    let points = decimate(&points, decimate_tolerance);
+
+    let mut builder = Path::builder();
+    let (first, rest) = match points.split_first() {
+        Some(split) => split,
+        None => return builder.build(),
+    };
+    builder.begin(*first);
+
+    if let LineMode::Smooth = mode {
+        // a monotone cubic through the points, written as beziers; each
+        // control point is a third of the way along its segment in x
+        let tangents = monotone_tangents(&points);
+        for i in 1..points.len() {
+            let (p1, p2) = (points[i - 1], points[i]);
+            let third = (p2.x - p1.x) / 3.0;
+
+            let ctrl1 = Point2D::new(p1.x + third, p1.y + tangents[i - 1] * third);
+            let ctrl2 = Point2D::new(p2.x - third, p2.y - tangents[i] * third);
+            builder.cubic_bezier_to(ctrl1, ctrl2, p2);
+        }
+    } else {
+        for pt in rest {
+            builder.line_to(*pt);
+        }
+    }
+
+    builder.end(false);
+    builder.build()
+}
+
+/// Get the slope of the curve at each point, such that the curve
+/// between two points never goes above or below both of them.
+///
+/// This uses the Fritsch-Butland weighted harmonic mean of the slopes
+/// on either side of each point, which also accounts for the uneven
+/// spacing left behind by decimation.
+fn monotone_tangents(points: &[Point2D<f32>]) -> Vec<f32> {
+    let slopes = points
+        .windows(2)
+        .map(|pair| (pair[1].y - pair[0].y) / (pair[1].x - pair[0].x))
+        .collect::<Vec<_>>();
+
+    let mut tangents = Vec::with_capacity(points.len());
+    tangents.extend(slopes.first().copied());
+
+    for i in 1..slopes.len() {
+        let (d0, d1) = (slopes[i - 1], slopes[i]);
+
+        // flatten out at peaks, valleys and plateaus
+        if d0 * d1 <= 0.0 {
+            tangents.push(0.0);
+            continue;
+        }
+
+        let h0 = points[i].x - points[i - 1].x;
+        let h1 = points[i + 1].x - points[i].x;
+        tangents.push(3.0 * (h0 + h1) / ((2.0 * h1 + h0) / d0 + (h1 + 2.0 * h0) / d1));
+    }
+
+    tangents.extend(slopes.last().copied());
+    tangents
+}
+
+/// Remove points that are within `tolerance` of the line through their
+/// neighbors, using the Ramer-Douglas-Peucker algorithm.
+fn decimate(points: &[Point2D<f32>], tolerance: f32) -> Vec<Point2D<f32>> {
+    if points.len() < 3 {
+        return points.to_vec();
+    }
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+
+    let mut ranges = vec![(0, points.len() - 1)];
+    while let Some((start, end)) = ranges.pop() {
+        let line = LineSegment {
+            from: points[start],
+            to: points[end],
+        };
+
+        // find the point furthest from the line between the endpoints
+        let furthest = (start + 1..end)
+            .map(|i| (i, line.distance_to_point(points[i])))
+            .fold(
+                None,
+                |furthest: Option<(usize, f32)>, (i, dist)| match furthest {
+                    Some((_, max)) if max >= dist => furthest,
+                    _ => Some((i, dist)),
+                },
+            );
+
+        if let Some((i, dist)) = furthest {
+            if dist > tolerance {
+                keep[i] = true;
+                ranges.push((start, i));
+                ranges.push((i, end));
+            }
+        }
+    }
+
+    points
+        .iter()
+        .zip(keep)
+        .filter_map(|(pt, keep)| if keep { Some(*pt) } else { None })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PathEvent;
+
+    fn count_segments(path: &Path) -> usize {
+        path.iter()
+            .filter(|event| matches!(event, PathEvent::Line { .. } | PathEvent::Cubic { .. }))
+            .count()
+    }
+
+    #[test]
+    fn test_decimation() {
+        // a straight line only needs its endpoints
+        let line = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let path = polyline_from_samples(&line, LineMode::Linear, 0.0);
+        assert_eq!(count_segments(&path), 1);
+
+        // small wobbles are only kept with a small tolerance
+        let wobbly = [0.0, 0.1, 0.0, 0.1, 0.0];
+        let path = polyline_from_samples(&wobbly, LineMode::Linear, 0.5);
+        assert_eq!(count_segments(&path), 1);
+        let path = polyline_from_samples(&wobbly, LineMode::Linear, 0.01);
+        assert_eq!(count_segments(&path), 4);
+    }
+
+    #[test]
+    fn test_line_modes() {
+        let samples = [0.0, 1.0, 0.0];
+
+        // every step is a run followed by a rise
+        let path = polyline_from_samples(&samples, LineMode::Step, 0.0);
+        assert_eq!(count_segments(&path), 4);
+
+        // the curve still passes through every kept sample
+        let path = polyline_from_samples(&samples, LineMode::Smooth, 0.0);
+        let ends = path
+            .iter()
+            .filter_map(|event| match event {
+                PathEvent::Cubic { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(ends, &[Point2D::new(1.0, 1.0), Point2D::new(2.0, 0.0)]);
+
+        let path = polyline_from_samples(&[], LineMode::Linear, 0.0);
+        assert_eq!(path.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_smooth_does_not_overshoot() {
+        // a long flat run that decimation reduces to a few uneven points
+        let mut samples = [0.0; 99];
+        samples[98] = 50.0;
+        let path = polyline_from_samples(&samples, LineMode::Smooth, 0.5);
+
+        for event in path.iter() {
+            if let PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } = event
+            {
+                let (min_y, max_y) = (from.y.min(to.y), from.y.max(to.y));
+                for ctrl in [ctrl1, ctrl2].iter() {
+                    assert!(ctrl.x >= from.x && ctrl.x <= to.x, "{:?}", ctrl);
+                    assert!(ctrl.y >= min_y && ctrl.y <= max_y, "{:?}", ctrl);
+                }
+            }
+        }
+    }
+}
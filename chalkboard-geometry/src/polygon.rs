@@ -32,8 +32,8 @@ impl Polygon {
     pub fn add_edge(&mut self, p1: Point2D<f32>, p2: Point2D<f32>) {
         let edge = Edge::new(p1, p2);
 
-        // don't add perfectly horizontal edges
-        if edge.line.vector.y.abs() >= f32::EPSILON {
+        // don't add edges that contribute nothing to the polygon
+        if keep_edge(&edge) {
             self.edges.push(edge);
         }
     }
@@ -64,9 +64,7 @@ impl IntoIterator for Polygon {
 impl FromIterator<Edge<f32>> for Polygon {
     fn from_iter<T: IntoIterator<Item = Edge<f32>>>(iter: T) -> Self {
         Self {
-            edges: iter.into_iter().filter(|edge| {
-                !approx_eq(edge.line.vector.y, 0.0)
-            }).collect() 
+            edges: iter.into_iter().filter(keep_edge).collect(),
         }
     }
 }
@@ -137,6 +135,26 @@ impl<Num: Scalar> Edge<Num> {
         }
     }
 
+    /// Tell whether or not every coordinate of this edge is finite.
+    pub fn is_finite(&self) -> bool {
+        let Line { point, vector } = self.line;
+        point.x.is_finite() && point.y.is_finite() && vector.x.is_finite() && vector.y.is_finite()
+    }
+
+    /// Tell whether or not this edge is degenerate.
+    ///
+    /// Degenerate edges are horizontal (including zero-length) edges, which
+    /// do not contribute any area, and edges with non-finite coordinates.
+    /// These are skipped when building a `Polygon`, so that every consumer
+    /// sees the same normalized geometry.
+    ///
+    /// Skipping a non-finite edge leaves the polygon open where that edge
+    /// would have been. Enable the `assert-finite` feature to catch these
+    /// with a debug assertion instead.
+    pub fn is_degenerate(&self) -> bool {
+        !self.is_finite() || approx_eq(self.line.vector.y, Num::zero())
+    }
+
     /// Get the two points for this edge.
     pub fn points(self) -> (Point2D<Num>, Point2D<Num>) {
         if approx_eq(self.line.vector.y, Num::zero()) {
@@ -171,6 +189,17 @@ impl<Num: Scalar> Edge<Num> {
     }
 }
 
+/// Tell whether an edge should be added to a `Polygon`.
+fn keep_edge(edge: &Edge<f32>) -> bool {
+    debug_assert!(
+        !cfg!(feature = "assert-finite") || edge.is_finite(),
+        "non-finite edge in polygon: {:?}",
+        edge,
+    );
+
+    !edge.is_degenerate()
+}
+
 /// The direction that an `Edge` moves in.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub enum Direction {
@@ -207,4 +236,41 @@ mod tests {
         let e2 = Edge::new(Point2D::new(1.0, 0.0), Point2D::new(2.0, 2.0));
         assert_eq!(e1.intersection(&e2), None);
     }
+
+    #[test]
+    #[cfg(not(all(feature = "assert-finite", debug_assertions)))]
+    fn test_degenerate_edges() {
+        // zero-length, horizontal and non-finite edges are degenerate
+        let zero = Edge::new(Point2D::new(1.0, 1.0), Point2D::new(1.0, 1.0));
+        let horiz = Edge::new(Point2D::new(0.0, 1.0), Point2D::new(5.0, 1.0));
+        let nan = Edge::new(Point2D::new(f32::NAN, 0.0), Point2D::new(1.0, 1.0));
+        let inf = Edge::new(Point2D::new(0.0, 0.0), Point2D::new(1.0, f32::INFINITY));
+        let good = Edge::new(Point2D::new(0.0, 0.0), Point2D::new(0.0, 1.0));
+
+        assert!(zero.is_degenerate());
+        assert!(horiz.is_degenerate());
+        assert!(nan.is_degenerate());
+        assert!(inf.is_degenerate());
+        assert!(!good.is_degenerate());
+
+        // they are skipped no matter how the polygon is built
+        let collected: Polygon = alloc::vec![zero, horiz, nan, inf, good]
+            .into_iter()
+            .collect();
+        assert_eq!(collected.edges.len(), 1);
+
+        let mut added = Polygon::default();
+        added.add_edge(Point2D::new(1.0, 1.0), Point2D::new(1.0, 1.0));
+        added.add_edge(Point2D::new(f32::NAN, 0.0), Point2D::new(1.0, 1.0));
+        added.add_edge(Point2D::new(0.0, 0.0), Point2D::new(0.0, 1.0));
+        assert_eq!(added.edges.len(), 1);
+    }
+
+    #[test]
+    #[cfg(all(feature = "assert-finite", debug_assertions))]
+    #[should_panic(expected = "non-finite edge")]
+    fn test_assert_finite() {
+        let mut polygon = Polygon::default();
+        polygon.add_edge(Point2D::new(f32::NAN, 0.0), Point2D::new(1.0, 1.0));
+    }
 }
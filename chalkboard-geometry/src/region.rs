@@ -3,9 +3,9 @@
 //       (See accompanying file LICENSE or copy at
 //         https://www.boost.org/LICENSE_1_0.txt)
 
-use core::iter::FromIterator;
+use core::{iter::FromIterator, ops::Add};
 
-use super::{Box2D, Point2D};
+use super::{Box2D, Point2D, Vector2D};
 use alloc::vec::Vec;
 use num_traits::Bounded;
 
@@ -27,7 +27,7 @@ impl<T: Bounded> Default for Region<T> {
     }
 }
 
-impl<T: Bounded + Copy + Ord> FromIterator<Box2D<T>> for Region<T> {
+impl<T: Bounded + Copy + PartialOrd> FromIterator<Box2D<T>> for Region<T> {
     fn from_iter<I: IntoIterator<Item = Box2D<T>>>(iter: I) -> Self {
         let mut region = Self::default();
         region.extend(iter);
@@ -35,7 +35,7 @@ impl<T: Bounded + Copy + Ord> FromIterator<Box2D<T>> for Region<T> {
     }
 }
 
-impl<T: Copy + Ord> Extend<Box2D<T>> for Region<T> {
+impl<T: Copy + PartialOrd> Extend<Box2D<T>> for Region<T> {
     fn extend<I: IntoIterator<Item = Box2D<T>>>(&mut self, iter: I) {
         let bounds = &mut self.bounds;
         let boxes = &mut self.boxes;
@@ -44,7 +44,7 @@ impl<T: Copy + Ord> Extend<Box2D<T>> for Region<T> {
     }
 }
 
-impl<T: Copy + Ord> Region<T> {
+impl<T: Copy + PartialOrd> Region<T> {
     /// Add new bounds to accomodate a box.
     fn accomodate(&mut self, box_: Box2D<T>) {
         add_to_bounds(&mut self.bounds, &box_)
@@ -57,6 +57,24 @@ impl<T: Copy + Ord> Region<T> {
     }
 }
 
+impl<T: Bounded + Copy + PartialOrd> Region<T> {
+    /// Get the part of this region that lies within a box.
+    pub fn intersection(&self, other: &Box2D<T>) -> Self {
+        self.boxes
+            .iter()
+            .filter_map(|box_| box_.intersection(other))
+            .collect()
+    }
+
+    /// Get this region, moved by the given vector.
+    pub fn translate(&self, by: Vector2D<T>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        self.boxes.iter().map(|box_| box_.translate(by)).collect()
+    }
+}
+
 impl<T: Copy> Region<T> {
     pub fn bounds(&self) -> Box2D<T> {
         self.bounds
@@ -68,9 +86,10 @@ impl<T: Copy> Region<T> {
 }
 
 fn default_bounds<T: Bounded>() -> Box2D<T> {
+    // start inverted, so that the first box added becomes the bounds
     Box2D::new(
-        Point2D::new(T::min_value(), T::min_value()),
         Point2D::new(T::max_value(), T::max_value()),
+        Point2D::new(T::min_value(), T::min_value()),
     )
 }
 
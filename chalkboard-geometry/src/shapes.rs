@@ -0,0 +1,92 @@
+//               Copyright John Nunley, 2022.
+// Distributed under the Boost Software License, Version 1.0.
+//       (See accompanying file LICENSE or copy at
+//         https://www.boost.org/LICENSE_1_0.txt)
+
+use super::{Angle, Arc, Path, Point2D, Vector2D};
+
+/// Build the path for a segment of a ring, or a "donut slice".
+///
+/// The segment lies between `inner_radius` and `outer_radius` around
+/// `center`, starting at `start_angle` and covering `sweep_angle`. The
+/// outer arc is traced in the direction of the sweep and the inner arc
+/// in the opposite direction, so the segment fills correctly under either
+/// fill rule, even when it covers the entire ring.
+///
+/// If `inner_radius` is zero, this is a pie slice.
+pub fn ring_segment(
+    center: Point2D<f32>,
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: Angle<f32>,
+    sweep_angle: Angle<f32>,
+) -> Path {
+    let arc = |radius: f32, start_angle, sweep_angle| Arc {
+        center,
+        radii: Vector2D::new(radius, radius),
+        start_angle,
+        sweep_angle,
+        x_rotation: Angle::zero(),
+    };
+
+    let outer = arc(outer_radius, start_angle, sweep_angle);
+    let mut builder = Path::builder();
+    builder.begin(outer.from());
+    outer.for_each_cubic_bezier(&mut |seg| {
+        builder.cubic_bezier_to(seg.ctrl1, seg.ctrl2, seg.to);
+    });
+
+    if inner_radius > 0.0 {
+        // trace the inner arc backwards, from the end of the outer arc
+        let inner = arc(inner_radius, start_angle + sweep_angle, -sweep_angle);
+        builder.line_to(inner.from());
+        inner.for_each_cubic_bezier(&mut |seg| {
+            builder.cubic_bezier_to(seg.ctrl1, seg.ctrl2, seg.to);
+        });
+    } else {
+        builder.line_to(center);
+    }
+
+    builder.close();
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{util::approx_eq, PathEvent};
+
+    #[test]
+    fn test_ring_segment() {
+        let center = Point2D::new(10.0, 10.0);
+        let path = ring_segment(center, 2.0, 4.0, Angle::zero(), Angle::frac_pi_2());
+
+        // every endpoint lies on either the inner or the outer circle
+        let mut lines = 0;
+        for event in path.iter() {
+            let to = match event {
+                PathEvent::Begin { at } => at,
+                PathEvent::Line { to, .. } => {
+                    lines += 1;
+                    to
+                }
+                PathEvent::Cubic { to, .. } => to,
+                PathEvent::End { close, .. } => {
+                    assert!(close);
+                    continue;
+                }
+                ev => panic!("unexpected event {:?}", ev),
+            };
+
+            let dist = (to - center).length();
+            assert!((dist - 2.0).abs() < 1e-4 || (dist - 4.0).abs() < 1e-4);
+        }
+
+        // one line joins the outer arc to the inner one
+        assert_eq!(lines, 1);
+
+        // the segment starts on the outer circle at the start angle
+        let first = path.first_endpoint().unwrap().0;
+        assert!(approx_eq(first.x, 14.0) && approx_eq(first.y, 10.0));
+    }
+}
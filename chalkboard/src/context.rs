@@ -33,6 +33,29 @@ pub struct Context<'a> {
     /// Note that references are niched, so the runtime cost of this
     /// is likely to be very small.
     draw_method: Option<&'a mut dyn DrawMethod>,
+    /// What to do when the current draw method does not support an
+    /// operation.
+    fallbacks: Fallbacks,
+}
+
+/// The policy used when a `DrawMethod` does not support an operation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum Fallbacks {
+    /// Return the unsupported error to the caller.
+    Error,
+    /// Retry the operation with the inner `DrawMethod`, and so on down
+    /// the chain.
+    ///
+    /// Whether or not this succeeds depends on what the inner methods
+    /// are able to do. Chains end in a `NoopDrawer`, whose error is
+    /// returned once no method is left to try. Every operation starts
+    /// again from the top of the chain.
+    ///
+    /// This is the default.
+    #[default]
+    FallThrough,
+    /// Pretend that the operation succeeded.
+    IgnoreSilently,
 }
 
 impl<'a> Context<'a> {
@@ -40,26 +63,44 @@ impl<'a> Context<'a> {
     pub fn new(draw_method: &'a mut dyn DrawMethod) -> Self {
         Context {
             draw_method: Some(draw_method),
+            fallbacks: Fallbacks::default(),
         }
     }
 
+    /// Get the current fallback policy.
+    pub fn fallbacks(&self) -> Fallbacks {
+        self.fallbacks
+    }
+
+    /// Set the policy used when an operation is unsupported.
+    pub fn set_fallbacks(&mut self, fallbacks: Fallbacks) {
+        self.fallbacks = fallbacks;
+    }
+
     /// Get the current draw method.
     fn draw_method(&mut self) -> &mut dyn DrawMethod {
         self.draw_method.as_mut().expect("DrawMethod is None")
     }
 
     /// Run a `draw` operation.
-    fn draw(&mut self, op: &DrawOperation<'_>) -> Result<()> {
+    ///
+    /// If the current draw method does not support the operation, the
+    /// fallback policy decides what happens next.
+    pub fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+        let fallbacks = self.fallbacks;
+        let mut dm = self.draw_method();
+
         loop {
             // try to draw using the current draw method
-            match self.draw_method().draw(op) {
-                // if the drawing is unsupported, move on
-                // to the next one
-                Err(e) if e.is_unsupported() => {
-                    let dm = self.draw_method.take().expect("DrawMethod is None");
-                    let next = dm.inner();
-                    self.draw_method = Some(next);
-                }
+            match dm.draw(op) {
+                // if the drawing is unsupported, consult the
+                // fallback policy
+                Err(e) if e.is_unsupported() => match fallbacks {
+                    Fallbacks::Error => return Err(e),
+                    Fallbacks::IgnoreSilently => return Ok(()),
+                    // move on to the next one, for this operation only
+                    Fallbacks::FallThrough => dm = dm.inner(),
+                },
                 res => return res,
             }
         }
@@ -71,3 +112,93 @@ impl<'a, D: DrawMethod> From<&'a mut D> for Context<'a> {
         Self::new(draw_method)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        draw_information::test_util::params,
+        draw_method::{OperationKind, RecordingDrawer},
+        Error,
+    };
+    use geometry::{PathBuffer, Region};
+
+    /// A draw method that only supports masks, backed by a recorder.
+    #[derive(Default)]
+    struct MasksOnly {
+        masks: usize,
+        inner: RecordingDrawer,
+    }
+
+    impl DrawMethod for MasksOnly {
+        fn inner(&mut self) -> &mut dyn DrawMethod {
+            &mut self.inner
+        }
+
+        fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+            match op {
+                DrawOperation::Mask { .. } => {
+                    self.masks += 1;
+                    Ok(())
+                }
+                _ => Err(Error::unsupported()),
+            }
+        }
+    }
+
+    fn draw_kind(ctx: &mut Context<'_>, kind: OperationKind) -> Result<()> {
+        let params = params(Region::default());
+        let mut op = match kind {
+            OperationKind::Mask => DrawOperation::Mask { params },
+            _ => DrawOperation::Fill {
+                params,
+                paths: PathBuffer::new(),
+            },
+        };
+
+        ctx.draw(&mut op)
+    }
+
+    fn fill_with(method: &mut MasksOnly, fallbacks: Fallbacks) -> Result<()> {
+        let mut ctx = Context::new(method);
+        ctx.set_fallbacks(fallbacks);
+        draw_kind(&mut ctx, OperationKind::Fill)
+    }
+
+    #[test]
+    fn test_fallbacks_error() {
+        let mut method = MasksOnly::default();
+        let err = fill_with(&mut method, Fallbacks::Error).unwrap_err();
+        assert!(err.is_unsupported());
+        assert!(method.inner.operations().is_empty());
+    }
+
+    #[test]
+    fn test_fallbacks_ignore_silently() {
+        let mut method = MasksOnly::default();
+        fill_with(&mut method, Fallbacks::IgnoreSilently).unwrap();
+        assert!(method.inner.operations().is_empty());
+    }
+
+    #[test]
+    fn test_fallbacks_fall_through() {
+        let mut method = MasksOnly::default();
+        fill_with(&mut method, Fallbacks::FallThrough).unwrap();
+        method.inner.assert_kinds(&[OperationKind::Fill]);
+    }
+
+    #[test]
+    fn test_fall_through_is_per_operation() {
+        let mut method = MasksOnly::default();
+
+        {
+            let mut ctx = Context::new(&mut method);
+            draw_kind(&mut ctx, OperationKind::Fill).unwrap();
+            draw_kind(&mut ctx, OperationKind::Mask).unwrap();
+        }
+
+        // the mask still goes to the top of the chain
+        assert_eq!(method.masks, 1);
+        method.inner.assert_kinds(&[OperationKind::Fill]);
+    }
+}
@@ -59,7 +59,7 @@ pub trait BoxDraw: Device {
 
         match pattern {
             SpecializedPattern::SolidColor(clr) => self.solid_color_boxes(dst, *clr, boxes),
-            SpecializedPattern::GeneralImage(img) => self.image_boxes(dst, img, *origin, boxes),
+            SpecializedPattern::GeneralImage(img) => self.image_boxes(dst, *img, *origin, boxes),
             SpecializedPattern::Surface(src) => self.blit_boxes(dst, *src, *origin, boxes),
         }
     }
@@ -16,7 +16,7 @@
 // <https://www.gnu.org/licenses/>.
 
 use super::Pattern;
-use geometry::{Clip, CompositeOperation, PathBuffer};
+use geometry::{Clip, CompositeOperation, PathBuffer, Transform2D};
 
 /// An operation for drawing on a surface.
 pub enum DrawOperation<'surf> {
@@ -34,16 +34,151 @@ pub enum DrawOperation<'surf> {
     },
 }
 
+impl<'surf> DrawOperation<'surf> {
+    /// Get the composite parameters for this operation.
+    pub fn params(&self) -> &CompositeParameters<'surf> {
+        match self {
+            DrawOperation::Mask { params }
+            | DrawOperation::Fill { params, .. }
+            | DrawOperation::Stroke { params, .. } => params,
+        }
+    }
+
+    /// Get the paths to fill or stroke, if this operation has any.
+    pub fn paths(&self) -> Option<&PathBuffer> {
+        match self {
+            DrawOperation::Mask { .. } => None,
+            DrawOperation::Fill { paths, .. } | DrawOperation::Stroke { paths, .. } => Some(paths),
+        }
+    }
+
+    /// Get a mutable reference to the paths to fill or stroke, if this
+    /// operation has any.
+    pub fn paths_mut(&mut self) -> Option<&mut PathBuffer> {
+        match self {
+            DrawOperation::Mask { .. } => None,
+            DrawOperation::Fill { paths, .. } | DrawOperation::Stroke { paths, .. } => Some(paths),
+        }
+    }
+
+    /// Get a mutable reference to the composite parameters for this
+    /// operation.
+    pub fn params_mut(&mut self) -> &mut CompositeParameters<'surf> {
+        match self {
+            DrawOperation::Mask { params }
+            | DrawOperation::Fill { params, .. }
+            | DrawOperation::Stroke { params, .. } => params,
+        }
+    }
+}
+
 /// Parameters for drawing on a surface.
 pub struct CompositeParameters<'surf> {
     /// The operation combining the source and mask.
-    operation: CompositeOperation,
+    pub(crate) operation: CompositeOperation,
     /// The clipping pattern, used to determine which
     /// areas, if any, to effect.
-    clip: Clip,
+    pub(crate) clip: Clip,
+    /// The transform from pattern space to surface space.
+    pub(crate) transform: Transform2D<f32>,
 
     /// The source pattern.
-    source: Pattern<'surf>,
+    pub(crate) source: Pattern<'surf>,
     /// The mask pattern.
-    mask: Pattern<'surf>,
+    pub(crate) mask: Pattern<'surf>,
+}
+
+impl<'surf> CompositeParameters<'surf> {
+    /// Create a new set of parameters, with an identity transform.
+    pub fn new(
+        operation: CompositeOperation,
+        clip: Clip,
+        source: Pattern<'surf>,
+        mask: Pattern<'surf>,
+    ) -> Self {
+        CompositeParameters {
+            operation,
+            clip,
+            transform: Transform2D::identity(),
+            source,
+            mask,
+        }
+    }
+
+    /// Get the operation combining the source and mask.
+    pub fn operation(&self) -> CompositeOperation {
+        self.operation
+    }
+
+    /// Get the clip for this operation.
+    pub fn clip(&self) -> &Clip {
+        &self.clip
+    }
+
+    /// Get the transform from pattern space to surface space.
+    pub fn transform(&self) -> Transform2D<f32> {
+        self.transform
+    }
+
+    /// Set the transform from pattern space to surface space.
+    pub fn set_transform(&mut self, transform: Transform2D<f32>) {
+        self.transform = transform;
+    }
+
+    /// Get the source pattern.
+    pub fn source(&self) -> &Pattern<'surf> {
+        &self.source
+    }
+
+    /// Get the mask pattern.
+    pub fn mask(&self) -> &Pattern<'surf> {
+        &self.mask
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::CompositeParameters;
+    use crate::Pattern;
+    use alloc::boxed::Box;
+    use core::any::Any;
+    use geometry::{Box2D, Clip, CompositeOperation, PathBuffer, Point2D, Region};
+
+    /// Get the box between two corners.
+    pub(crate) fn boxed(x1: f32, y1: f32, x2: f32, y2: f32) -> Box2D<f32> {
+        Box2D::new(Point2D::new(x1, y1), Point2D::new(x2, y2))
+    }
+
+    /// The box that tests clip to.
+    pub(crate) fn clip_box() -> Box2D<f32> {
+        boxed(1.0, 2.0, 3.0, 4.0)
+    }
+
+    /// A region made up of only `clip_box()`.
+    pub(crate) fn clip_region() -> Region<f32> {
+        core::iter::once(clip_box()).collect()
+    }
+
+    /// Parameters that composite `Over` within the region, with empty
+    /// surfaces as the source and mask.
+    pub(crate) fn params(region: Region<f32>) -> CompositeParameters<'static> {
+        params_with::<()>(region)
+    }
+
+    /// Parameters that composite `Over` within the region, with surfaces
+    /// of type `S` as the source and mask.
+    pub(crate) fn params_with<S: Any + Default>(
+        region: Region<f32>,
+    ) -> CompositeParameters<'static> {
+        // the surfaces are tiny and only live as long as the test
+        let src: &'static mut S = Box::leak(Box::default());
+        let mask: &'static mut S = Box::leak(Box::default());
+
+        CompositeParameters::new(
+            CompositeOperation::Over,
+            Clip::new(PathBuffer::new(), region),
+            Pattern::Surface(src),
+            Pattern::Surface(mask),
+        )
+    }
 }
@@ -0,0 +1,503 @@
+// This file is part of chalkboard.
+//
+// chalkboard is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// chalkboard is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with chalkboard. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::{DrawMethod, NoopDrawer};
+use crate::{
+    device::{CompositeDraw, PatternAndOrigin},
+    CompositeParameters, Device, DrawOperation, Error, NotSupportedOp, Result,
+};
+use alloc::vec::Vec;
+use geometry::{Box2D, PathBuffer, PathEvent, Point2D, Region, Transform2D, Vector2D};
+
+/// A `DrawMethod` that draws by lowering operations into a series of
+/// composite operations on the underlying surface.
+///
+/// This is useful for devices that follow the "XRender" model of
+/// drawing, where everything is a combination of a source, a mask and
+/// a destination.
+///
+/// Fills are drawn directly when the paths are non-overlapping,
+/// axis-aligned rectangles. Other fills and all strokes are unsupported,
+/// and are left for the next `DrawMethod` in the chain.
+pub struct CompositeMethod<'surf, Dev: Device + ?Sized> {
+    // device/surface to draw onto
+    device: &'surf mut Dev,
+    surface: &'surf mut Dev::Surface,
+
+    // underlying NoopDrawer for when we're compromised
+    noop: NoopDrawer,
+}
+
+impl<'surf, Dev: CompositeDraw + ?Sized> CompositeMethod<'surf, Dev> {
+    /// Create a new `CompositeMethod` for the given device and surface.
+    pub fn new(device: &'surf mut Dev, surface: &'surf mut Dev::Surface) -> Self {
+        CompositeMethod {
+            device,
+            surface,
+            noop: NoopDrawer,
+        }
+    }
+
+    /// Get a reference to the underlying device.
+    pub fn device(&self) -> &Dev {
+        self.device
+    }
+
+    /// Get a mutable reference to the underlying device.
+    pub fn device_mut(&mut self) -> &mut Dev {
+        self.device
+    }
+
+    /// Get a reference to the underlying surface.
+    pub fn surface(&self) -> &Dev::Surface {
+        self.surface
+    }
+
+    /// Get a mutable reference to the underlying surface.
+    pub fn surface_mut(&mut self) -> &mut Dev::Surface {
+        self.surface
+    }
+
+    /// Get a tuple of references to the device and surface.
+    pub fn device_and_surface(&self) -> (&Dev, &Dev::Surface) {
+        (self.device, self.surface)
+    }
+
+    /// Get a tuple of mutable references to the device and surface.
+    pub fn device_and_surface_mut(&mut self) -> (&mut Dev, &mut Dev::Surface) {
+        (self.device, self.surface)
+    }
+
+    /// Convert this `DrawMethod` into the underlying
+    /// device and surface.
+    pub fn into_device_and_surface(self) -> (&'surf mut Dev, &'surf mut Dev::Surface) {
+        let Self {
+            device, surface, ..
+        } = self;
+        (device, surface)
+    }
+}
+
+impl<'surf, Dev: CompositeDraw + ?Sized> DrawMethod for CompositeMethod<'surf, Dev> {
+    fn inner(&mut self) -> &mut dyn DrawMethod {
+        &mut self.noop
+    }
+
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+        match op {
+            DrawOperation::Mask { params } => composite_mask(self.device, self.surface, params),
+            DrawOperation::Fill { params, paths } => {
+                composite_fill(self.device, self.surface, params, paths)
+            }
+            // stroking requires the paths to be rasterized into a mask
+            // first, which the composite model alone can't do
+            DrawOperation::Stroke { .. } => Err(Error::unsupported_op(NotSupportedOp::Strokes)),
+        }
+    }
+}
+
+/// Composite the source onto the surface through the mask, over the
+/// area covered by the clip.
+///
+/// The clip is in surface space, while the transform is applied to the
+/// source and mask patterns.
+///
+/// This is shared between all of the `DrawMethod`s for devices that
+/// implement `CompositeDraw`.
+pub(crate) fn composite_mask<Dev: CompositeDraw + ?Sized>(
+    device: &mut Dev,
+    surface: &mut Dev::Surface,
+    params: &mut CompositeParameters<'_>,
+) -> Result<()> {
+    composite_area(device, surface, params, None)
+}
+
+/// Fill in the paths by compositing the source onto the surface through
+/// the mask, over the area covered by both the paths and the clip.
+///
+/// This only works for paths that are made up of axis-aligned
+/// rectangles that do not overlap, since those can be drawn as a set of
+/// boxes. Anything else is unsupported.
+pub(crate) fn composite_fill<Dev: CompositeDraw + ?Sized>(
+    device: &mut Dev,
+    surface: &mut Dev::Surface,
+    params: &mut CompositeParameters<'_>,
+    paths: &PathBuffer,
+) -> Result<()> {
+    let boxes = match path_boxes(paths) {
+        Some(boxes) => boxes,
+        None => return Err(Error::unsupported_op(NotSupportedOp::Fills)),
+    };
+
+    // only draw where the paths and the clip overlap
+    let clip = params.clip.region();
+    let area = boxes
+        .iter()
+        .flat_map(|path_box| {
+            clip.boxes()
+                .iter()
+                .filter_map(move |clip_box| clip_box.intersection(path_box))
+        })
+        .collect::<Region<f32>>();
+
+    composite_area(device, surface, params, Some(&area))
+}
+
+/// Composite the source onto the surface through the mask, over the
+/// given area or over the clip if there is none.
+///
+/// This handles what every lowered operation has in common: checking the
+/// clip, turning the transform into a pattern origin and specializing
+/// the source and mask for the device.
+fn composite_area<Dev: CompositeDraw + ?Sized>(
+    device: &mut Dev,
+    surface: &mut Dev::Surface,
+    params: &mut CompositeParameters<'_>,
+    area: Option<&Region<f32>>,
+) -> Result<()> {
+    let CompositeParameters {
+        operation,
+        clip,
+        transform,
+        source,
+        mask,
+    } = params;
+
+    // clipping by an arbitrary path can't be expressed as a set of
+    // boxes
+    if !clip.path().is_empty() {
+        return Err(Error::unsupported_op(NotSupportedOp::ClipPaths));
+    }
+
+    // patterns can only be offset by their origin, so anything beyond
+    // a translation needs another method
+    let origin = match pattern_origin(transform) {
+        Some(origin) => origin,
+        None => {
+            return Err(Error::unsupported_op(NotSupportedOp::Transforms(
+                *transform,
+            )))
+        }
+    };
+
+    let source = PatternAndOrigin {
+        pattern: source.specialize(device)?,
+        origin,
+    };
+    let mask = PatternAndOrigin {
+        pattern: mask.specialize(device)?,
+        origin,
+    };
+    let boxes = area
+        .unwrap_or_else(|| clip.region())
+        .boxes()
+        .iter()
+        .copied();
+
+    device.composite_boxes(*operation, surface, source, mask, boxes)
+}
+
+/// Get the boxes covered by a set of paths, if every path is an
+/// axis-aligned rectangle and none of them overlap.
+///
+/// Overlapping rectangles would be composited twice where they meet,
+/// which is not what filling them means.
+fn path_boxes(paths: &PathBuffer) -> Option<Vec<Box2D<f32>>> {
+    let mut boxes: Vec<Box2D<f32>> = Vec::with_capacity(paths.len());
+    let mut points = Vec::with_capacity(5);
+
+    for path in paths.iter() {
+        points.clear();
+
+        for event in path.iter() {
+            match event {
+                PathEvent::Begin { at } => points.push(at),
+                PathEvent::Line { to, .. } => points.push(to),
+                PathEvent::End { .. } => {}
+                // curves can't be expressed as boxes
+                _ => return None,
+            }
+        }
+
+        // an explicit line back to the start closes the path
+        if points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+
+        let box_ = rectangle(&points)?;
+        if boxes.iter().any(|other| other.intersects(&box_)) {
+            return None;
+        }
+        boxes.push(box_);
+    }
+
+    Some(boxes)
+}
+
+/// Get the box that the points trace out, if they are the four corners
+/// of an axis-aligned rectangle in order.
+fn rectangle(points: &[Point2D<f32>]) -> Option<Box2D<f32>> {
+    if points.len() != 4 {
+        return None;
+    }
+
+    let box_ = Box2D::from_points(points);
+    if box_.is_empty() {
+        return None;
+    }
+
+    let is_corner = |pt: &Point2D<f32>| {
+        (pt.x == box_.min.x || pt.x == box_.max.x) && (pt.y == box_.min.y || pt.y == box_.max.y)
+    };
+    let distinct = (0..4).all(|i| (i + 1..4).all(|j| points[i] != points[j]));
+    // every side has to be horizontal or vertical
+    let axis_aligned = (0..4).all(|i| {
+        let (a, b) = (points[i], points[(i + 1) % 4]);
+        a.x == b.x || a.y == b.y
+    });
+
+    (points.iter().all(is_corner) && distinct && axis_aligned).then_some(box_)
+}
+
+/// Get the pattern origin corresponding to a transform, if the
+/// transform is a pure translation.
+fn pattern_origin(transform: &Transform2D<f32>) -> Option<Vector2D<f32>> {
+    let Transform2D {
+        m11,
+        m12,
+        m21,
+        m22,
+        m31,
+        m32,
+        ..
+    } = *transform;
+
+    let is_translation = m11 == 1.0 && m12 == 0.0 && m21 == 0.0 && m22 == 1.0;
+
+    // the pattern coordinate at the surface's origin
+    is_translation.then(|| Vector2D::new(-m31, -m32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        device::BoxDraw,
+        draw_information::test_util::{clip_box, clip_region, params_with},
+    };
+    use genimage::{Image, Rgba};
+    use geometry::{Clip, CompositeOperation};
+
+    #[derive(Default)]
+    struct MockSurface;
+
+    #[derive(Default)]
+    struct MockDevice {
+        composites: Vec<(CompositeOperation, Vector2D<f32>, Vec<Box2D<f32>>)>,
+    }
+
+    impl Device for MockDevice {
+        type Surface = MockSurface;
+    }
+
+    impl BoxDraw for MockDevice {
+        fn solid_color_boxes(
+            &mut self,
+            _: &mut MockSurface,
+            _: Rgba,
+            _: impl Iterator<Item = Box2D<f32>>,
+        ) -> Result<()> {
+            unreachable!()
+        }
+
+        fn image_boxes(
+            &mut self,
+            _: &mut MockSurface,
+            _: &impl Image,
+            _: Vector2D<f32>,
+            _: impl Iterator<Item = Box2D<f32>>,
+        ) -> Result<()> {
+            unreachable!()
+        }
+
+        fn blit_boxes(
+            &mut self,
+            _: &mut MockSurface,
+            _: &mut MockSurface,
+            _: Vector2D<f32>,
+            _: impl Iterator<Item = Box2D<f32>>,
+        ) -> Result<()> {
+            unreachable!()
+        }
+    }
+
+    impl CompositeDraw for MockDevice {
+        fn composite_boxes(
+            &mut self,
+            op: CompositeOperation,
+            _: &mut MockSurface,
+            src: PatternAndOrigin<'_, Self>,
+            mask: PatternAndOrigin<'_, Self>,
+            boxes: impl Iterator<Item = Box2D<f32>>,
+        ) -> Result<()> {
+            assert_eq!(src.origin, mask.origin);
+            self.composites.push((op, src.origin, boxes.collect()));
+            Ok(())
+        }
+    }
+
+    fn draw_mask(
+        device: &mut MockDevice,
+        path: PathBuffer,
+        transform: Transform2D<f32>,
+    ) -> Result<()> {
+        let mut surface = MockSurface;
+        let mut params = params_with::<MockSurface>(clip_region());
+        params.clip = Clip::new(path, clip_region());
+        params.set_transform(transform);
+
+        CompositeMethod::new(device, &mut surface).draw(&mut DrawOperation::Mask { params })
+    }
+
+    #[test]
+    fn test_mask_parameters() {
+        let mut device = MockDevice::default();
+        draw_mask(
+            &mut device,
+            PathBuffer::new(),
+            Transform2D::translation(5.0, 6.0),
+        )
+        .unwrap();
+
+        let (op, origin, boxes) = &device.composites[0];
+        assert_eq!(*op, CompositeOperation::Over);
+        assert_eq!(*origin, Vector2D::new(-5.0, -6.0));
+        assert_eq!(boxes.as_slice(), &[clip_box()]);
+    }
+
+    #[test]
+    fn test_mask_unsupported() {
+        let mut device = MockDevice::default();
+
+        // non-translation transforms
+        let res = draw_mask(&mut device, PathBuffer::new(), Transform2D::scale(2.0, 2.0));
+        assert_eq!(
+            res.unwrap_err().not_supported_op(),
+            Some(NotSupportedOp::Transforms(Transform2D::scale(2.0, 2.0)))
+        );
+
+        // clipping by a path
+        let mut path = PathBuffer::new();
+        let mut builder = path.builder();
+        builder.begin(Point2D::new(0.0, 0.0));
+        builder.line_to(Point2D::new(1.0, 1.0));
+        builder.end(true);
+        builder.build();
+        let res = draw_mask(&mut device, path, Transform2D::identity());
+        assert_eq!(
+            res.unwrap_err().not_supported_op(),
+            Some(NotSupportedOp::ClipPaths)
+        );
+
+        assert!(device.composites.is_empty());
+    }
+
+    fn rect_path(paths: &mut PathBuffer, x1: f32, y1: f32, x2: f32, y2: f32) {
+        let mut builder = paths.builder();
+        builder.begin(Point2D::new(x1, y1));
+        builder.line_to(Point2D::new(x2, y1));
+        builder.line_to(Point2D::new(x2, y2));
+        builder.line_to(Point2D::new(x1, y2));
+        builder.end(true);
+        builder.build();
+    }
+
+    fn draw_paths(device: &mut MockDevice, paths: PathBuffer, stroke: bool) -> Result<()> {
+        let mut surface = MockSurface;
+        let params = params_with::<MockSurface>(clip_region());
+        let mut op = if stroke {
+            DrawOperation::Stroke { params, paths }
+        } else {
+            DrawOperation::Fill { params, paths }
+        };
+
+        CompositeMethod::new(device, &mut surface).draw(&mut op)
+    }
+
+    #[test]
+    fn test_fill_rectangles() {
+        let mut device = MockDevice::default();
+        let mut paths = PathBuffer::new();
+        rect_path(&mut paths, 0.0, 0.0, 2.0, 3.0);
+        rect_path(&mut paths, 2.5, 3.5, 10.0, 10.0);
+        // outside of the clip entirely
+        rect_path(&mut paths, 20.0, 20.0, 30.0, 30.0);
+        draw_paths(&mut device, paths, false).unwrap();
+
+        // only the parts inside of the clip are composited
+        let (op, origin, boxes) = &device.composites[0];
+        assert_eq!(*op, CompositeOperation::Over);
+        assert_eq!(*origin, Vector2D::zero());
+        assert_eq!(
+            boxes.as_slice(),
+            &[
+                Box2D::new(Point2D::new(1.0, 2.0), Point2D::new(2.0, 3.0)),
+                Box2D::new(Point2D::new(2.5, 3.5), Point2D::new(3.0, 4.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_unsupported() {
+        let mut device = MockDevice::default();
+
+        // a triangle
+        let mut paths = PathBuffer::new();
+        let mut builder = paths.builder();
+        builder.begin(Point2D::new(0.0, 0.0));
+        builder.line_to(Point2D::new(4.0, 0.0));
+        builder.line_to(Point2D::new(0.0, 4.0));
+        builder.end(true);
+        builder.build();
+        let res = draw_paths(&mut device, paths, false);
+        assert_eq!(
+            res.unwrap_err().not_supported_op(),
+            Some(NotSupportedOp::Fills)
+        );
+
+        // overlapping rectangles
+        let mut paths = PathBuffer::new();
+        rect_path(&mut paths, 0.0, 0.0, 3.0, 3.0);
+        rect_path(&mut paths, 2.0, 2.0, 4.0, 4.0);
+        let res = draw_paths(&mut device, paths, false);
+        assert_eq!(
+            res.unwrap_err().not_supported_op(),
+            Some(NotSupportedOp::Fills)
+        );
+
+        // strokes of any kind
+        let mut paths = PathBuffer::new();
+        rect_path(&mut paths, 0.0, 0.0, 3.0, 3.0);
+        let res = draw_paths(&mut device, paths, true);
+        assert_eq!(
+            res.unwrap_err().not_supported_op(),
+            Some(NotSupportedOp::Strokes)
+        );
+
+        assert!(device.composites.is_empty());
+    }
+}
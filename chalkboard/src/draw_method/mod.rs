@@ -17,12 +17,24 @@
 
 use crate::{DrawOperation, Result};
 
+mod composite;
+pub use composite::CompositeMethod;
+
 mod noop;
 pub use noop::NoopDrawer;
 
+mod recording;
+pub use recording::{OperationKind, RecordedOperation, RecordedPattern, RecordingDrawer};
+
+mod snapping;
+pub use snapping::{SnapPolicy, SnappingDrawer};
+
 mod trapezoids;
 pub use trapezoids::TrapezoidMethod;
 
+mod validating;
+pub use validating::ValidatingDrawer;
+
 /// The backing interface for drawing things.
 /// 
 /// # Blocking
@@ -36,7 +48,10 @@ pub trait DrawMethod {
     fn inner(&mut self) -> &mut dyn DrawMethod;
 
     /// Run a `DrawOperation`.
-    fn draw(&mut self, op: &DrawOperation<'_>) -> Result<()>;
+    ///
+    /// If this returns an unsupported error, the same operation may be
+    /// passed on to the inner `DrawMethod`.
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()>;
 }
 
 impl<D: DrawMethod + ?Sized> DrawMethod for &mut D {
@@ -44,7 +59,7 @@ impl<D: DrawMethod + ?Sized> DrawMethod for &mut D {
         D::inner(self)
     }
 
-    fn draw(&mut self, op: &DrawOperation<'_>) -> Result<()> {
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
         D::draw(self, op)
     }
 }
@@ -29,7 +29,7 @@ impl DrawMethod for NoopDrawer {
         self
     }
 
-    fn draw(&mut self, _: &DrawOperation<'_>) -> Result<()> {
+    fn draw(&mut self, _: &mut DrawOperation<'_>) -> Result<()> {
         Err(crate::Error::from_display(
             "Attempted to draw with a NoopDrawer",
         ))
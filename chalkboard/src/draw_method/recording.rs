@@ -0,0 +1,288 @@
+// This file is part of chalkboard.
+//
+// chalkboard is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// chalkboard is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with chalkboard. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::{DrawMethod, NoopDrawer};
+use crate::{DrawOperation, Pattern, Result};
+use alloc::vec::Vec;
+use core::fmt;
+use genimage::Rgba;
+use geometry::{Clip, CompositeOperation, PathBuffer, Transform2D};
+
+/// A `DrawMethod` that records every operation it is given.
+///
+/// This allows code that draws to be tested without a real device.
+/// Every operation succeeds, and can be inspected afterwards.
+#[derive(Default)]
+pub struct RecordingDrawer {
+    // the operations recorded so far
+    operations: Vec<RecordedOperation>,
+
+    // underlying NoopDrawer to terminate the chain
+    noop: NoopDrawer,
+}
+
+/// An operation recorded by a `RecordingDrawer`.
+#[derive(Clone)]
+pub struct RecordedOperation {
+    /// The kind of operation.
+    pub kind: OperationKind,
+    /// The operation combining the source and mask.
+    pub operation: CompositeOperation,
+    /// The clip for the operation.
+    pub clip: Clip,
+    /// The transform from pattern space to surface space.
+    pub transform: Transform2D<f32>,
+    /// The source pattern.
+    pub source: RecordedPattern,
+    /// The mask pattern.
+    pub mask: RecordedPattern,
+    /// The paths that were filled or stroked.
+    ///
+    /// This is empty for mask operations.
+    pub paths: PathBuffer,
+}
+
+/// The kind of a `DrawOperation`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OperationKind {
+    /// A straight-composite operation.
+    Mask,
+    /// Filling in paths.
+    Fill,
+    /// Outlining the strokes of paths.
+    Stroke,
+}
+
+/// A pattern recorded by a `RecordingDrawer`.
+///
+/// Images and surfaces are borrowed by the operation, so only their
+/// presence is recorded.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RecordedPattern {
+    /// A solid color.
+    SolidColor(Rgba),
+    /// A `GeneralImage`.
+    GeneralImage,
+    /// A surface.
+    Surface,
+}
+
+impl RecordingDrawer {
+    /// Create a new, empty `RecordingDrawer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the operations recorded so far.
+    pub fn operations(&self) -> &[RecordedOperation] {
+        &self.operations
+    }
+
+    /// Get the number of operations of the given kind that were recorded.
+    pub fn count(&self, kind: OperationKind) -> usize {
+        self.operations.iter().filter(|op| op.kind == kind).count()
+    }
+
+    /// Forget all of the operations recorded so far.
+    pub fn clear(&mut self) {
+        self.operations.clear();
+    }
+
+    /// Take the operations recorded so far, leaving this drawer empty.
+    pub fn take_operations(&mut self) -> Vec<RecordedOperation> {
+        core::mem::take(&mut self.operations)
+    }
+
+    /// Assert that the recorded operations are exactly of the given
+    /// kinds, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the recorded operations differ from `expected`.
+    #[track_caller]
+    pub fn assert_kinds(&self, expected: &[OperationKind]) {
+        let kinds = self.operations.iter().map(|op| op.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds, expected,
+            "recorded operations did not match the expected operations"
+        );
+    }
+}
+
+impl DrawMethod for RecordingDrawer {
+    fn inner(&mut self) -> &mut dyn DrawMethod {
+        &mut self.noop
+    }
+
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+        let (kind, paths) = match op {
+            DrawOperation::Mask { .. } => (OperationKind::Mask, PathBuffer::new()),
+            DrawOperation::Fill { paths, .. } => (OperationKind::Fill, paths.clone()),
+            DrawOperation::Stroke { paths, .. } => (OperationKind::Stroke, paths.clone()),
+        };
+        let params = op.params();
+
+        self.operations.push(RecordedOperation {
+            kind,
+            operation: params.operation(),
+            clip: params.clip().clone(),
+            transform: params.transform(),
+            source: params.source().into(),
+            mask: params.mask().into(),
+            paths,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'a, 'surf> From<&'a Pattern<'surf>> for RecordedPattern {
+    fn from(pattern: &'a Pattern<'surf>) -> Self {
+        match pattern {
+            Pattern::SolidColor(color) => RecordedPattern::SolidColor(*color),
+            Pattern::GeneralImage(_) => RecordedPattern::GeneralImage,
+            Pattern::Surface(_) => RecordedPattern::Surface,
+        }
+    }
+}
+
+impl fmt::Debug for RecordingDrawer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecordingDrawer")
+            .field("operations", &self.operations)
+            .finish()
+    }
+}
+
+impl fmt::Debug for RecordedOperation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Paths(usize);
+
+        impl fmt::Debug for Paths {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "<{} paths>", self.0)
+            }
+        }
+
+        f.debug_struct("RecordedOperation")
+            .field("kind", &self.kind)
+            .field("operation", &self.operation)
+            .field("clip", self.clip.region())
+            .field("transform", &self.transform)
+            .field("source", &self.source)
+            .field("mask", &self.mask)
+            .field("paths", &Paths(self.paths.len()))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw_information::test_util::{clip_box, clip_region, params};
+    use geometry::Point2D;
+
+    fn triangle() -> PathBuffer {
+        let mut paths = PathBuffer::new();
+        let mut builder = paths.builder();
+        builder.begin(Point2D::new(0.0, 0.0));
+        builder.line_to(Point2D::new(4.0, 0.0));
+        builder.line_to(Point2D::new(0.0, 4.0));
+        builder.end(true);
+        builder.build();
+        paths
+    }
+
+    /// Record one operation of each kind.
+    fn record_all(drawer: &mut RecordingDrawer) {
+        let kinds = [
+            OperationKind::Mask,
+            OperationKind::Fill,
+            OperationKind::Stroke,
+        ];
+
+        for kind in kinds.iter() {
+            let mut params = params(clip_region());
+            params.set_transform(Transform2D::translation(5.0, 6.0));
+
+            let mut op = match kind {
+                OperationKind::Mask => DrawOperation::Mask { params },
+                OperationKind::Fill => DrawOperation::Fill {
+                    params,
+                    paths: triangle(),
+                },
+                OperationKind::Stroke => DrawOperation::Stroke {
+                    params,
+                    paths: triangle(),
+                },
+            };
+            drawer.draw(&mut op).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_recorded_fields() {
+        let mut drawer = RecordingDrawer::new();
+        record_all(&mut drawer);
+
+        drawer.assert_kinds(&[
+            OperationKind::Mask,
+            OperationKind::Fill,
+            OperationKind::Stroke,
+        ]);
+        for op in drawer.operations() {
+            assert_eq!(op.operation, CompositeOperation::Over);
+            assert_eq!(op.clip.region().boxes(), &[clip_box()]);
+            assert_eq!(op.transform, Transform2D::translation(5.0, 6.0));
+            assert_eq!(op.source, RecordedPattern::Surface);
+            assert_eq!(op.mask, RecordedPattern::Surface);
+        }
+
+        // only fills and strokes have paths
+        let ops = drawer.operations();
+        assert!(ops[0].paths.is_empty());
+        assert_eq!(ops[1].paths.len(), 1);
+        assert_eq!(ops[2].paths.len(), 1);
+    }
+
+    #[test]
+    fn test_counting_and_taking() {
+        let mut drawer = RecordingDrawer::new();
+        record_all(&mut drawer);
+        record_all(&mut drawer);
+
+        assert_eq!(drawer.count(OperationKind::Mask), 2);
+        assert_eq!(drawer.count(OperationKind::Fill), 2);
+        assert_eq!(drawer.count(OperationKind::Stroke), 2);
+
+        let taken = drawer.take_operations();
+        assert_eq!(taken.len(), 6);
+        assert!(drawer.operations().is_empty());
+
+        record_all(&mut drawer);
+        drawer.clear();
+        drawer.assert_kinds(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "recorded operations did not match")]
+    fn test_assert_kinds_mismatch() {
+        let mut drawer = RecordingDrawer::new();
+        record_all(&mut drawer);
+        drawer.assert_kinds(&[OperationKind::Fill]);
+    }
+}
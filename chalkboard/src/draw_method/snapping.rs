@@ -0,0 +1,157 @@
+// This file is part of chalkboard.
+//
+// chalkboard is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// chalkboard is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with chalkboard. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::DrawMethod;
+use crate::{DrawOperation, Result};
+use geometry::{map_paths, Point2D};
+
+/// The axes on which path coordinates are rounded to whole pixels.
+///
+/// Snapping only one axis keeps horizontal lines such as text
+/// baselines, or vertical lines such as separators, crisp without
+/// changing their position along the other axis.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum SnapPolicy {
+    /// Leave every coordinate as it is.
+    #[default]
+    None,
+    /// Round x coordinates only.
+    XOnly,
+    /// Round y coordinates only.
+    YOnly,
+    /// Round both x and y coordinates.
+    Both,
+}
+
+/// A `DrawMethod` that rounds the coordinates of the paths being filled
+/// or stroked to whole pixels, before passing the operation on to the
+/// `DrawMethod` it wraps.
+///
+/// The paths are snapped in place, so an operation that falls back to
+/// the next method in the chain draws the same snapped geometry. Mask
+/// operations and clips are left alone.
+#[derive(Debug, Default)]
+pub struct SnappingDrawer<D> {
+    inner: D,
+    policy: SnapPolicy,
+}
+
+impl<D: DrawMethod> SnappingDrawer<D> {
+    /// Wrap a `DrawMethod`, snapping according to the given policy.
+    pub fn new(inner: D, policy: SnapPolicy) -> Self {
+        SnappingDrawer { inner, policy }
+    }
+
+    /// Get the current snapping policy.
+    pub fn policy(&self) -> SnapPolicy {
+        self.policy
+    }
+
+    /// Set the snapping policy used for later operations.
+    pub fn set_policy(&mut self, policy: SnapPolicy) {
+        self.policy = policy;
+    }
+
+    /// Get a reference to the wrapped `DrawMethod`.
+    pub fn get_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the wrapped `DrawMethod`.
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Unwrap the wrapped `DrawMethod`.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: DrawMethod> DrawMethod for SnappingDrawer<D> {
+    fn inner(&mut self) -> &mut dyn DrawMethod {
+        self.inner.inner()
+    }
+
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+        let (x, y) = match self.policy {
+            SnapPolicy::None => (false, false),
+            SnapPolicy::XOnly => (true, false),
+            SnapPolicy::YOnly => (false, true),
+            SnapPolicy::Both => (true, true),
+        };
+
+        if x || y {
+            if let Some(paths) = op.paths_mut() {
+                *paths = map_paths(paths, |pt| {
+                    let rounded = pt.round();
+                    Point2D::new(
+                        if x { rounded.x } else { pt.x },
+                        if y { rounded.y } else { pt.y },
+                    )
+                });
+            }
+        }
+
+        self.inner.draw(op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{draw_information::test_util::params, draw_method::RecordingDrawer};
+    use alloc::vec::Vec;
+    use geometry::{PathBuffer, PathEvent, Region};
+
+    fn snapped(policy: SnapPolicy) -> Vec<Point2D<f32>> {
+        let mut paths = PathBuffer::new();
+        let mut builder = paths.builder();
+        builder.begin(Point2D::new(1.3, 2.7));
+        builder.line_to(Point2D::new(4.6, 2.2));
+        builder.end(false);
+        builder.build();
+
+        let mut drawer = SnappingDrawer::new(RecordingDrawer::new(), policy);
+        let mut op = DrawOperation::Stroke {
+            params: params(Region::default()),
+            paths,
+        };
+        drawer.draw(&mut op).unwrap();
+
+        drawer.get_ref().operations()[0]
+            .paths
+            .iter()
+            .flat_map(|path| path.iter())
+            .filter_map(|event| match event {
+                PathEvent::Begin { at } => Some(at),
+                PathEvent::Line { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_snap_policies() {
+        let pt = Point2D::new;
+
+        assert_eq!(snapped(SnapPolicy::None), &[pt(1.3, 2.7), pt(4.6, 2.2)]);
+        assert_eq!(snapped(SnapPolicy::XOnly), &[pt(1.0, 2.7), pt(5.0, 2.2)]);
+        assert_eq!(snapped(SnapPolicy::YOnly), &[pt(1.3, 3.0), pt(4.6, 2.0)]);
+        assert_eq!(snapped(SnapPolicy::Both), &[pt(1.0, 3.0), pt(5.0, 2.0)]);
+    }
+}
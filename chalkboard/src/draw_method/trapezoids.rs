@@ -15,8 +15,11 @@
 // Public License along with chalkboard. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use super::{DrawMethod, NoopDrawer};
-use crate::{device::TrapezoidDraw, Device, DrawOperation, Result};
+use super::{
+    composite::{composite_fill, composite_mask},
+    DrawMethod, NoopDrawer,
+};
+use crate::{device::TrapezoidDraw, Device, DrawOperation, Error, NotSupportedOp, Result};
 
 /// A `DrawMethod` that draws by rendering paths as trapezoids and then
 /// painting them to the underlying surface.
@@ -84,8 +87,17 @@ impl<'surf, Dev: TrapezoidDraw + ?Sized> DrawMethod for TrapezoidMethod<'surf, D
         &mut self.noop
     }
 
-    fn draw(&mut self, op: &DrawOperation<'_>) -> Result<()> {
-        todo!()
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+        match op {
+            DrawOperation::Mask { params } => composite_mask(self.device, self.surface, params),
+            // paths aren't tessellated into trapezoids yet, so only
+            // rectangles can be filled for now
+            DrawOperation::Fill { params, paths } => {
+                composite_fill(self.device, self.surface, params, paths)
+            }
+            // neither are strokes
+            DrawOperation::Stroke { .. } => Err(Error::unsupported_op(NotSupportedOp::Strokes)),
+        }
     }
 }
 
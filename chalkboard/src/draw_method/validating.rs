@@ -0,0 +1,272 @@
+// This file is part of chalkboard.
+//
+// chalkboard is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// chalkboard is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with chalkboard. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use super::DrawMethod;
+use crate::{error::InvalidInput, DrawOperation, Error, Result};
+use geometry::{PathBuffer, PathEvent, Point2D, Transform2D};
+
+/// A `DrawMethod` that checks every operation before passing it on to
+/// the `DrawMethod` it wraps.
+///
+/// This catches mistakes such as non-finite coordinates or filling an
+/// empty set of paths, which would otherwise behave differently on every
+/// device. Devices themselves don't check for these, so that they can
+/// stay lean; wrap a `DrawMethod` in this while debugging instead.
+///
+/// In debug builds, an invalid operation causes a panic describing the
+/// problem. In release builds, an invalid input error is returned
+/// instead.
+///
+/// Only the wrapped `DrawMethod` is guarded. `inner()` returns the next
+/// method in the chain as-is, so operations drawn on it directly are not
+/// checked. When a `Context` falls back after an unsupported error, it
+/// retries the same operation, which has already been checked by then.
+#[derive(Debug, Default)]
+pub struct ValidatingDrawer<D> {
+    inner: D,
+}
+
+impl<D: DrawMethod> ValidatingDrawer<D> {
+    /// Wrap a `DrawMethod`.
+    pub fn new(inner: D) -> Self {
+        ValidatingDrawer { inner }
+    }
+
+    /// Get a reference to the wrapped `DrawMethod`.
+    pub fn get_ref(&self) -> &D {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the wrapped `DrawMethod`.
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.inner
+    }
+
+    /// Unwrap the wrapped `DrawMethod`.
+    pub fn into_inner(self) -> D {
+        self.inner
+    }
+}
+
+impl<D: DrawMethod> DrawMethod for ValidatingDrawer<D> {
+    fn inner(&mut self) -> &mut dyn DrawMethod {
+        self.inner.inner()
+    }
+
+    fn draw(&mut self, op: &mut DrawOperation<'_>) -> Result<()> {
+        if let Err(err) = validate(op) {
+            if cfg!(debug_assertions) {
+                panic!("invalid DrawOperation: {}", err);
+            }
+
+            return Err(err);
+        }
+
+        self.inner.draw(op)
+    }
+}
+
+/// Check a `DrawOperation` for invalid input.
+fn validate(op: &DrawOperation<'_>) -> Result<()> {
+    let params = op.params();
+
+    if !transform_is_finite(&params.transform()) {
+        return Err(Error::invalid_input(InvalidInput::NonFiniteTransform));
+    }
+
+    let clip = params.clip();
+    let region_is_finite = clip
+        .region()
+        .boxes()
+        .iter()
+        .all(|b| point_is_finite(b.min) && point_is_finite(b.max));
+    if !region_is_finite || !paths_are_finite(clip.path()) {
+        return Err(Error::invalid_input(InvalidInput::NonFiniteClip));
+    }
+
+    match op {
+        DrawOperation::Mask { .. } => {}
+        DrawOperation::Fill { paths, .. } | DrawOperation::Stroke { paths, .. } => {
+            if paths.iter().all(|path| path.is_empty()) {
+                return Err(Error::invalid_input(InvalidInput::EmptyPaths));
+            }
+
+            if !paths_are_finite(paths) {
+                return Err(Error::invalid_input(InvalidInput::NonFinitePath));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn point_is_finite(pt: Point2D<f32>) -> bool {
+    pt.x.is_finite() && pt.y.is_finite()
+}
+
+fn transform_is_finite(transform: &Transform2D<f32>) -> bool {
+    transform.to_array().iter().all(|m| m.is_finite())
+}
+
+fn paths_are_finite(paths: &PathBuffer) -> bool {
+    paths
+        .iter()
+        .flat_map(|path| path.iter())
+        .all(|event| match event {
+            PathEvent::Begin { at } => point_is_finite(at),
+            PathEvent::Line { from, to } => point_is_finite(from) && point_is_finite(to),
+            PathEvent::Quadratic { from, ctrl, to } => {
+                point_is_finite(from) && point_is_finite(ctrl) && point_is_finite(to)
+            }
+            PathEvent::Cubic {
+                from,
+                ctrl1,
+                ctrl2,
+                to,
+            } => {
+                point_is_finite(from)
+                    && point_is_finite(ctrl1)
+                    && point_is_finite(ctrl2)
+                    && point_is_finite(to)
+            }
+            PathEvent::End { last, first, .. } => point_is_finite(last) && point_is_finite(first),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        draw_information::test_util::{boxed, params},
+        draw_method::RecordingDrawer,
+    };
+    use alloc::string::{String, ToString};
+    use geometry::Region;
+
+    fn fill_op(
+        paths: PathBuffer,
+        transform: Transform2D<f32>,
+        region: Region<f32>,
+    ) -> DrawOperation<'static> {
+        let mut params = params(region);
+        params.set_transform(transform);
+        DrawOperation::Fill { params, paths }
+    }
+
+    fn fill(
+        paths: PathBuffer,
+        transform: Transform2D<f32>,
+        drawer: &mut dyn DrawMethod,
+    ) -> Result<()> {
+        drawer.draw(&mut fill_op(paths, transform, Region::default()))
+    }
+
+    fn check(
+        paths: PathBuffer,
+        transform: Transform2D<f32>,
+        region: Region<f32>,
+    ) -> core::result::Result<(), String> {
+        validate(&fill_op(paths, transform, region)).map_err(|err| {
+            assert!(err.is_invalid_input());
+            err.to_string()
+        })
+    }
+
+    fn line() -> PathBuffer {
+        let mut paths = PathBuffer::new();
+        let mut builder = paths.builder();
+        builder.begin(Point2D::new(0.0, 0.0));
+        builder.line_to(Point2D::new(1.0, 1.0));
+        builder.end(true);
+        builder.build();
+        paths
+    }
+
+    #[test]
+    fn test_valid_passes_through() {
+        let mut drawer = ValidatingDrawer::new(RecordingDrawer::new());
+        fill(line(), Transform2D::identity(), &mut drawer).unwrap();
+        assert_eq!(drawer.get_ref().operations().len(), 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "non-finite component")]
+    fn test_non_finite_transform() {
+        let mut drawer = ValidatingDrawer::new(RecordingDrawer::new());
+        let transform = Transform2D::translation(f32::INFINITY, 0.0);
+        let _ = fill(line(), transform, &mut drawer);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "no paths")]
+    fn test_empty_paths() {
+        let mut drawer = ValidatingDrawer::new(RecordingDrawer::new());
+        let _ = fill(PathBuffer::new(), Transform2D::identity(), &mut drawer);
+    }
+
+    #[test]
+    fn test_validate() {
+        let id = Transform2D::identity();
+        assert_eq!(check(line(), id, Region::default()), Ok(()));
+
+        let transform = Transform2D::translation(0.0, f32::NAN);
+        assert_eq!(
+            check(line(), transform, Region::default()).unwrap_err(),
+            "The transform has a non-finite component"
+        );
+
+        let region = core::iter::once(boxed(0.0, 0.0, f32::INFINITY, 1.0)).collect();
+        assert_eq!(
+            check(line(), id, region).unwrap_err(),
+            "The clip has a non-finite coordinate"
+        );
+
+        assert_eq!(
+            check(PathBuffer::new(), id, Region::default()).unwrap_err(),
+            "There are no paths to draw"
+        );
+    }
+
+    // lyon refuses to build paths with non-finite points when debug
+    // assertions are on, so these can only come up in release builds
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_validate_non_finite_path() {
+        let mut paths = PathBuffer::new();
+        let mut builder = paths.builder();
+        builder.begin(Point2D::new(0.0, 0.0));
+        builder.line_to(Point2D::new(f32::INFINITY, 1.0));
+        builder.end(true);
+        builder.build();
+
+        assert_eq!(
+            check(paths, Transform2D::identity(), Region::default()).unwrap_err(),
+            "A path has a non-finite coordinate"
+        );
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn test_invalid_returns_error() {
+        let mut drawer = ValidatingDrawer::new(RecordingDrawer::new());
+        let err = fill(PathBuffer::new(), Transform2D::identity(), &mut drawer).unwrap_err();
+        assert!(err.is_invalid_input());
+        assert!(drawer.get_ref().operations().is_empty());
+    }
+}
@@ -17,19 +17,65 @@
 
 use alloc::string::{String, ToString};
 use core::fmt;
+use geometry::{CompositeOperation, Transform2D};
 
 pub struct Error {
     kind: Kind,
 }
 
 enum Kind {
-    Unsupported,
+    Unsupported(Option<NotSupportedOp>),
     InvalidInput(InvalidInput),
     Display(String),
 }
 
 #[derive(Debug, Copy, Clone)]
-pub(crate) enum InvalidInput {}
+pub(crate) enum InvalidInput {
+    /// A path to fill or stroke has a non-finite coordinate.
+    NonFinitePath,
+    /// The clip has a non-finite coordinate.
+    NonFiniteClip,
+    /// The transform has a non-finite component.
+    NonFiniteTransform,
+    /// There are no paths to fill or stroke.
+    EmptyPaths,
+}
+
+/// The capability that was missing when an operation was unsupported.
+///
+/// Where it makes sense, this includes the value that was requested, so
+/// that callers can fall back precisely.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum NotSupportedOp {
+    /// Drawing with partial transparency.
+    Transparency,
+    /// Coordinates that are not on integer boundaries.
+    Floats,
+    /// Using images of a certain format.
+    ///
+    /// Image formats belong to `genimage`, which does not describe them
+    /// as a value yet, so the format is not included.
+    ImageFormats,
+    /// A certain composite operation.
+    CompositeOp(CompositeOperation),
+    /// Filling in paths.
+    Fills,
+    /// Outlining the strokes of paths.
+    Strokes,
+    /// Dashed strokes.
+    DashedStrokes,
+    /// Clipping by an arbitrary path.
+    ClipPaths,
+    /// Drawing text.
+    Text,
+    /// A certain transform.
+    Transforms(Transform2D<f32>),
+    /// Blurring.
+    Blurs,
+    /// Gradient patterns.
+    Gradients,
+}
 
 impl Error {
     /// Create a new error from an error-like type.
@@ -39,16 +85,44 @@ impl Error {
         }
     }
 
+    /// Create a new invalid input error.
+    pub(crate) fn invalid_input(input: InvalidInput) -> Self {
+        Error {
+            kind: Kind::InvalidInput(input),
+        }
+    }
+
     /// Create a new unsupported error.
     pub fn unsupported() -> Self {
         Error {
-            kind: Kind::Unsupported,
+            kind: Kind::Unsupported(None),
+        }
+    }
+
+    /// Create a new unsupported error for a specific capability.
+    pub fn unsupported_op(op: NotSupportedOp) -> Self {
+        Error {
+            kind: Kind::Unsupported(Some(op)),
         }
     }
 
     /// Is this error an unsupported error?
     pub fn is_unsupported(&self) -> bool {
-        matches!(self.kind, Kind::Unsupported)
+        matches!(self.kind, Kind::Unsupported(_))
+    }
+
+    /// Is this error caused by invalid input?
+    pub fn is_invalid_input(&self) -> bool {
+        matches!(self.kind, Kind::InvalidInput(_))
+    }
+
+    /// Get the capability that was missing, if this is an unsupported
+    /// error that knows it.
+    pub fn not_supported_op(&self) -> Option<NotSupportedOp> {
+        match self.kind {
+            Kind::Unsupported(op) => op,
+            _ => None,
+        }
     }
 }
 
@@ -59,7 +133,8 @@ impl fmt::Debug for Error {
         impl<'a> fmt::Debug for KindFmt<'a> {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
                 match self.0 {
-                    Kind::Unsupported => f.write_str("Unsupported"),
+                    Kind::Unsupported(None) => f.write_str("Unsupported"),
+                    Kind::Unsupported(Some(op)) => f.debug_tuple("Unsupported").field(op).finish(),
                     Kind::InvalidInput(i) => fmt::Debug::fmt(i, f),
                     Kind::Display(s) => write!(f, r#""{}""#, s),
                 }
@@ -73,11 +148,63 @@ impl fmt::Debug for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
-            Kind::Unsupported => f.write_str("Attempted to run an unsupported operation"),
-            Kind::InvalidInput(i) => match i {},
+            Kind::Unsupported(None) => f.write_str("Attempted to run an unsupported operation"),
+            Kind::Unsupported(Some(ref op)) => {
+                write!(f, "Attempted to run an unsupported operation: {}", op)
+            }
+            Kind::InvalidInput(i) => f.write_str(match i {
+                InvalidInput::NonFinitePath => "A path has a non-finite coordinate",
+                InvalidInput::NonFiniteClip => "The clip has a non-finite coordinate",
+                InvalidInput::NonFiniteTransform => "The transform has a non-finite component",
+                InvalidInput::EmptyPaths => "There are no paths to draw",
+            }),
             Kind::Display(ref msg) => f.write_str(msg),
         }
     }
 }
 
+impl fmt::Display for NotSupportedOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotSupportedOp::Transparency => f.write_str("partial transparency"),
+            NotSupportedOp::Floats => f.write_str("non-integer coordinates"),
+            NotSupportedOp::ImageFormats => f.write_str("the image format"),
+            NotSupportedOp::CompositeOp(op) => write!(f, "the {:?} composite operation", op),
+            NotSupportedOp::Fills => f.write_str("filling paths"),
+            NotSupportedOp::Strokes => f.write_str("stroking paths"),
+            NotSupportedOp::DashedStrokes => f.write_str("dashed strokes"),
+            NotSupportedOp::ClipPaths => f.write_str("clipping by a path"),
+            NotSupportedOp::Text => f.write_str("drawing text"),
+            NotSupportedOp::Transforms(_) => f.write_str("the requested transform"),
+            NotSupportedOp::Blurs => f.write_str("blurs"),
+            NotSupportedOp::Gradients => f.write_str("gradient patterns"),
+        }
+    }
+}
+
 pub type Result<T = ()> = core::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_unsupported_display() {
+        let err = Error::unsupported_op(NotSupportedOp::Transforms(Transform2D::scale(2.0, 2.0)));
+        assert_eq!(
+            format!("{}", err),
+            "Attempted to run an unsupported operation: the requested transform"
+        );
+
+        let err = Error::unsupported_op(NotSupportedOp::CompositeOp(CompositeOperation::Src));
+        assert_eq!(
+            format!("{}", err),
+            "Attempted to run an unsupported operation: the Src composite operation"
+        );
+        assert_eq!(
+            err.not_supported_op(),
+            Some(NotSupportedOp::CompositeOp(CompositeOperation::Src))
+        );
+    }
+}
@@ -37,9 +37,9 @@ macro_rules! cfg_async {
 extern crate alloc;
 
 mod context;
-pub use context::Context;
+pub use context::{Context, Fallbacks};
 
-mod device;
+pub mod device;
 pub use device::Device;
 
 mod draw_information;
@@ -49,7 +49,12 @@ pub mod draw_method;
 pub use draw_method::DrawMethod;
 
 mod error;
-pub use error::{Error, Result};
+pub use error::{Error, NotSupportedOp, Result};
 
 mod pattern;
 pub use pattern::{Pattern, SpecializedPattern};
+
+pub mod prelude;
+
+mod split;
+pub use split::split_by_region;
@@ -15,8 +15,8 @@
 // Public License along with chalkboard. If not, see
 // <https://www.gnu.org/licenses/>.
 
-use crate::Device;
-use core::{any::Any, fmt, result::Result};
+use crate::{Device, Error, Result};
+use core::{any::Any, fmt};
 use genimage::{GeneralImage, Rgba};
 
 /// A pattern acts as a source or a mask in composition operations.
@@ -40,22 +40,34 @@ pub enum SpecializedPattern<'surf, Dev: Device + ?Sized> {
     /// A solid color.
     SolidColor(Rgba),
     /// Any pattern that may be represented as a `GeneralImage`.
-    GeneralImage(GeneralImage<&'surf mut [u8]>),
+    GeneralImage(&'surf GeneralImage<&'surf mut [u8]>),
     /// Use a surface as a source.
     Surface(&'surf mut Dev::Surface),
 }
 
 impl<'surf> Pattern<'surf> {
-    pub fn specialize<D: Device + ?Sized>(
-        self,
+    /// Specialize this pattern for the given `Device`.
+    ///
+    /// The pattern is borrowed for as long as the specialized pattern
+    /// lives, so that it can be specialized again if the operation
+    /// needs to be retried with another `DrawMethod`.
+    ///
+    /// This returns an error if the pattern is a surface that does not
+    /// belong to the `Device`.
+    pub fn specialize<'a, D: Device + ?Sized>(
+        &'a mut self,
         device: &mut D,
-    ) -> Result<SpecializedPattern<'surf, D>, Pattern<'surf>> {
+    ) -> Result<SpecializedPattern<'a, D>> {
         Ok(match self {
-            Pattern::SolidColor(color) => SpecializedPattern::SolidColor(color),
+            Pattern::SolidColor(color) => SpecializedPattern::SolidColor(*color),
             Pattern::GeneralImage(image) => SpecializedPattern::GeneralImage(image),
-            Pattern::Surface(surface) => match device.cast_our_surface(surface) {
+            Pattern::Surface(surface) => match device.cast_our_surface(&mut **surface) {
                 Ok(surf) => SpecializedPattern::Surface(surf),
-                Err(surface) => return Err(Pattern::Surface(surface)),
+                Err(_) => {
+                    return Err(Error::from_display(
+                        "Attempted to use a surface that does not belong to this device",
+                    ))
+                }
             },
         })
     }
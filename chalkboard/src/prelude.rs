@@ -0,0 +1,49 @@
+// This file is part of chalkboard.
+//
+// chalkboard is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// chalkboard is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with chalkboard. If not, see
+// <https://www.gnu.org/licenses/>.
+
+//! A prelude containing the most commonly used items.
+//!
+//! This is intended to be glob imported:
+//!
+//! ```
+//! use chalkboard::prelude::*;
+//! ```
+
+#[doc(no_inline)]
+pub use crate::{
+    device::{BoxDraw, CompositeDraw, ImageMapDraw, SyncDevice, TrapezoidDraw},
+    CompositeParameters, Context, Device, DrawMethod, DrawOperation, Error, Fallbacks,
+    NotSupportedOp, Pattern,
+};
+
+// `Result` is left out on purpose, since it would shadow the standard
+// library's `Result` for anyone glob importing this module.
+
+#[doc(no_inline)]
+pub use genimage::Rgba;
+
+// the path builder helpers
+#[doc(no_inline)]
+pub use geometry::{
+    offset_path, polyline_from_samples, ring_segment, translate_paths, LineJoin, LineMode,
+};
+
+#[doc(no_inline)]
+pub use geometry::{
+    Angle, Box2D, Clip, CompositeOperation, DamageAccumulator, FillRule, Path, PathBuffer, Point2D,
+    Rect, Region, Size2D, Transform2D, Vector2D,
+};
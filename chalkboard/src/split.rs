@@ -0,0 +1,165 @@
+// This file is part of chalkboard.
+//
+// chalkboard is free software: you can redistribute it and/or
+// modify it under the terms of the GNU Lesser General Public
+// License as published by the Free Software Foundation,
+// either version 3 of the License, or (at your option)
+// any later version.
+//
+// chalkboard is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty
+// of MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.
+// See the GNU Lesser General Public License for more details.
+//
+// You should have received a copy of the GNU Lesser General
+// Public License along with chalkboard. If not, see
+// <https://www.gnu.org/licenses/>.
+
+use crate::{DrawOperation, Result};
+use core::mem;
+use geometry::{translate_paths, Box2D, Region};
+
+/// Run a `DrawOperation` once for every band of a `Region`.
+///
+/// This is useful for devices that can only draw into banded buffers,
+/// such as multiple monitors or remote protocols. For every band that the
+/// operation's clip overlaps, `f` is called with that band and the
+/// operation. During the call, the clip is restricted to the band and
+/// the clip, transform and any paths are moved so that the band's origin
+/// is at `(0, 0)`.
+///
+/// The original clip, transform and paths are restored before this
+/// function returns, even if `f` returns an error.
+pub fn split_by_region(
+    op: &mut DrawOperation<'_>,
+    bands: &Region<f32>,
+    mut f: impl FnMut(&Box2D<f32>, &mut DrawOperation<'_>) -> Result<()>,
+) -> Result<()> {
+    for band in bands.boxes() {
+        let params = op.params_mut();
+        let offset = -band.min.to_vector();
+
+        let clip = params.clip.intersection(band);
+        if clip.region().boxes().is_empty() {
+            continue;
+        }
+
+        // move the operation into the band's coordinates
+        let old_clip = mem::replace(&mut params.clip, clip.translate(offset));
+        let old_transform = params.transform;
+        params.transform = old_transform.then_translate(offset);
+        let old_paths = op
+            .paths_mut()
+            .map(|paths| mem::replace(paths, translate_paths(paths, offset)));
+
+        let res = f(band, op);
+
+        let params = op.params_mut();
+        params.clip = old_clip;
+        params.transform = old_transform;
+        if let (Some(paths), Some(old_paths)) = (op.paths_mut(), old_paths) {
+            *paths = old_paths;
+        }
+
+        res?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::draw_information::test_util::{boxed, params};
+    use alloc::vec::Vec;
+    use geometry::{PathBuffer, PathEvent, Point2D, Transform2D};
+
+    #[test]
+    fn test_split_by_region() {
+        let mut op = DrawOperation::Mask {
+            params: params(core::iter::once(boxed(5.0, 0.0, 15.0, 10.0)).collect()),
+        };
+
+        // three side-by-side bands, the last of which the clip misses
+        let bands = [
+            boxed(0.0, 0.0, 10.0, 10.0),
+            boxed(10.0, 0.0, 20.0, 10.0),
+            boxed(20.0, 0.0, 30.0, 10.0),
+        ]
+        .iter()
+        .copied()
+        .collect::<Region<f32>>();
+
+        let mut seen = Vec::new();
+        split_by_region(&mut op, &bands, |band, op| {
+            let params = op.params();
+            seen.push((
+                *band,
+                params.clip().region().boxes().to_vec(),
+                params.transform(),
+            ));
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[0].1, &[boxed(5.0, 0.0, 10.0, 10.0)]);
+        assert_eq!(seen[0].2, Transform2D::identity());
+        assert_eq!(seen[1].1, &[boxed(0.0, 0.0, 5.0, 10.0)]);
+        assert_eq!(seen[1].2, Transform2D::translation(-10.0, 0.0));
+
+        // the operation is left as it was
+        let params = op.params();
+        assert_eq!(
+            params.clip().region().boxes(),
+            &[boxed(5.0, 0.0, 15.0, 10.0)]
+        );
+        assert_eq!(params.transform(), Transform2D::identity());
+    }
+
+    fn first_point(paths: &PathBuffer) -> Point2D<f32> {
+        match paths.iter().next().and_then(|path| path.iter().next()) {
+            Some(PathEvent::Begin { at }) => at,
+            _ => panic!("no paths"),
+        }
+    }
+
+    #[test]
+    fn test_split_paths_by_region() {
+        for stroke in [false, true] {
+            let params = params(core::iter::once(boxed(0.0, 0.0, 20.0, 10.0)).collect());
+
+            let mut paths = PathBuffer::new();
+            let mut builder = paths.builder();
+            builder.begin(Point2D::new(12.0, 1.0));
+            builder.line_to(Point2D::new(18.0, 1.0));
+            builder.line_to(Point2D::new(18.0, 9.0));
+            builder.end(true);
+            builder.build();
+
+            let mut op = if stroke {
+                DrawOperation::Stroke { params, paths }
+            } else {
+                DrawOperation::Fill { params, paths }
+            };
+
+            let bands = [boxed(0.0, 0.0, 10.0, 10.0), boxed(10.0, 0.0, 20.0, 10.0)]
+                .iter()
+                .copied()
+                .collect::<Region<f32>>();
+
+            let mut seen = Vec::new();
+            split_by_region(&mut op, &bands, |_, op| {
+                seen.push(first_point(op.paths().unwrap()));
+                Ok(())
+            })
+            .unwrap();
+
+            // the paths are in the same coordinates as the clip
+            assert_eq!(seen, &[Point2D::new(12.0, 1.0), Point2D::new(2.0, 1.0)]);
+
+            // and are put back afterwards
+            assert_eq!(first_point(op.paths().unwrap()), Point2D::new(12.0, 1.0));
+        }
+    }
+}